@@ -0,0 +1,112 @@
+//! Procedural macros for embedded-lang
+//! These are re-exported by the `embedded-lang` crate, and should be used from there
+#![warn(missing_docs)]
+
+use proc_macro::{Literal, Span, TokenStream, TokenTree};
+use std::path::{Component, Path, PathBuf};
+
+/// Embed the resources declared by path in a language file, such as `"resources": {"logo": "./logo.png"}`
+/// Paths are relative to the language file, which is relative to the file the macro is used in
+/// Expands to a `HashMap<String, Vec<u8>>` of the resources
+///
+/// # Arguments
+/// * `filename` - Path to the language file
+#[proc_macro]
+pub fn declared_resources(input: TokenStream) -> TokenStream {
+    match expand(input) {
+        Ok(tokens) => tokens,
+        Err(e) => format!("compile_error!({:?})", e).parse().unwrap(),
+    }
+}
+
+fn expand(input: TokenStream) -> Result<TokenStream, String> {
+    let literal = match input.into_iter().collect::<Vec<_>>().as_slice() {
+        [TokenTree::Literal(literal)] => literal.clone(),
+        [TokenTree::Group(group)] => {
+            match group.stream().into_iter().collect::<Vec<_>>().as_slice() {
+                [TokenTree::Literal(literal)] => literal.clone(),
+                _ => return Err("expected the path of a language file".to_string()),
+            }
+        }
+        _ => return Err("expected the path of a language file".to_string()),
+    };
+
+    let filename = parse_string(&literal)?;
+    let path = base_directory(literal.span()).join(&filename);
+    let json = std::fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let value: serde_json::Value =
+        serde_json::from_str(&json).map_err(|e| format!("{}: {}", path.display(), e))?;
+
+    let directory = path.parent().unwrap_or(Path::new("")).to_path_buf();
+    let mut entries = Vec::new();
+    if let Some(resources) = value.get("resources").and_then(|r| r.as_object()) {
+        for (name, resource) in resources {
+            match resource.as_str() {
+                Some(data) if data.starts_with("data:") => continue,
+                Some(resource_path) => {
+                    if !Path::new(resource_path)
+                        .components()
+                        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+                    {
+                        return Err(format!(
+                            "{}: resource paths must be inside the language's directory",
+                            resource_path
+                        ));
+                    }
+                    let resource_path = directory.join(resource_path);
+                    let resource_path = resource_path
+                        .canonicalize()
+                        .map_err(|e| format!("{}: {}", resource_path.display(), e))?;
+                    let resource_path = resource_path
+                        .to_str()
+                        .ok_or_else(|| format!("{}: path is not utf8", resource_path.display()))?;
+                    entries.push(format!(
+                        "({:?}.to_string(), include_bytes!({:?}).to_vec())",
+                        name, resource_path
+                    ));
+                }
+                None => continue,
+            }
+        }
+    }
+
+    format!(
+        "::std::collections::HashMap::<::std::string::String, ::std::vec::Vec<u8>>::from([{}])",
+        entries.join(", ")
+    )
+    .parse()
+    .map_err(|e| format!("{:?}", e))
+}
+
+/// Directory paths are relative to, matching `include_str!`
+fn base_directory(span: Span) -> PathBuf {
+    span.local_file()
+        .and_then(|file| file.parent().map(Path::to_path_buf))
+        .or_else(|| std::env::var("CARGO_MANIFEST_DIR").ok().map(PathBuf::from))
+        .unwrap_or_default()
+}
+
+/// Read the value of a plain string literal
+fn parse_string(literal: &Literal) -> Result<String, String> {
+    let source = literal.to_string();
+    let inner = source
+        .strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .ok_or_else(|| format!("expected a string literal, found {}", source))?;
+
+    let mut value = String::new();
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('\\') => value.push('\\'),
+                Some('"') => value.push('"'),
+                Some('n') => value.push('\n'),
+                Some('t') => value.push('\t'),
+                other => return Err(format!("unsupported escape in path: \\{:?}", other)),
+            },
+            c => value.push(c),
+        }
+    }
+    Ok(value)
+}
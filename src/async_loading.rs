@@ -0,0 +1,317 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use crate::serialization::resource_path;
+use crate::{Language, LanguageSet};
+
+/// A place language packs can be loaded from, such as a directory, a server, or memory
+/// Implementations can be written with `async fn`
+pub trait LanguageSource: Send + Sync {
+    /// List the codes of the languages the source provides
+    fn languages(&self) -> impl Future<Output = Result<Vec<String>, String>> + Send;
+
+    /// Read the JSON of a language
+    ///
+    /// # Arguments
+    /// * `language` - Code of the language
+    fn read_language(&self, language: &str)
+        -> impl Future<Output = Result<Vec<u8>, String>> + Send;
+
+    /// Read a resource declared in a language's `resources` section
+    ///
+    /// # Arguments
+    /// * `language` - Code of the language declaring the resource
+    /// * `path` - Path of the resource, as written in the language
+    fn read_resource(
+        &self,
+        language: &str,
+        path: &str,
+    ) -> impl Future<Output = Result<Vec<u8>, String>> + Send;
+}
+
+/// Language packs stored as `.lang.json` files in a directory, such as `fr.lang.json`
+/// Resource paths are relative to the directory, and must stay inside it
+#[derive(Clone, Debug)]
+pub struct DirectorySource {
+    path: PathBuf,
+}
+
+impl DirectorySource {
+    /// Create a source reading from a directory
+    ///
+    /// # Arguments
+    /// * `path` - Path to the directory
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl LanguageSource for DirectorySource {
+    async fn languages(&self) -> Result<Vec<String>, String> {
+        let mut entries = tokio::fs::read_dir(&self.path)
+            .await
+            .map_err(|e| e.to_string())?;
+        let mut languages = vec![];
+        while let Some(entry) = entries.next_entry().await.map_err(|e| e.to_string())? {
+            if let Some(code) = entry
+                .file_name()
+                .to_string_lossy()
+                .strip_suffix(".lang.json")
+            {
+                languages.push(code.to_string());
+            }
+        }
+        languages.sort();
+        Ok(languages)
+    }
+
+    async fn read_language(&self, language: &str) -> Result<Vec<u8>, String> {
+        read_file(&self.path.join(format!("{}.lang.json", language))).await
+    }
+
+    async fn read_resource(&self, _language: &str, path: &str) -> Result<Vec<u8>, String> {
+        read_file(&resource_path(&self.path, path)?).await
+    }
+}
+
+/// Language packs held in memory, such as in tests or when fetched by other means
+/// Resources are found by the path they are declared with, whichever language declares them
+#[derive(Clone, Debug, Default)]
+pub struct MemorySource {
+    languages: HashMap<String, Vec<u8>>,
+    resources: HashMap<String, Vec<u8>>,
+}
+
+impl MemorySource {
+    /// Create an empty source
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the JSON of a language to the source
+    ///
+    /// # Arguments
+    /// * `language` - Code of the language
+    /// * `json` - JSON of the language
+    pub fn with_language<B: Into<Vec<u8>>>(mut self, language: &str, json: B) -> Self {
+        self.languages.insert(language.to_string(), json.into());
+        self
+    }
+
+    /// Add a resource to the source
+    ///
+    /// # Arguments
+    /// * `path` - Path the resource is declared with, such as `./logo.png`
+    /// * `bytes` - Contents of the resource
+    pub fn with_resource<B: Into<Vec<u8>>>(mut self, path: &str, bytes: B) -> Self {
+        self.resources.insert(path.to_string(), bytes.into());
+        self
+    }
+}
+
+impl LanguageSource for MemorySource {
+    async fn languages(&self) -> Result<Vec<String>, String> {
+        let mut languages: Vec<String> = self.languages.keys().cloned().collect();
+        languages.sort();
+        Ok(languages)
+    }
+
+    async fn read_language(&self, language: &str) -> Result<Vec<u8>, String> {
+        self.languages
+            .get(language)
+            .cloned()
+            .ok_or_else(|| format!("{} is not available", language))
+    }
+
+    async fn read_resource(&self, _language: &str, path: &str) -> Result<Vec<u8>, String> {
+        self.resources
+            .get(path)
+            .cloned()
+            .ok_or_else(|| format!("{} is not available", path))
+    }
+}
+
+impl Language {
+    /// Read language from an async reader, such as a file or a network stream
+    /// Resources declared by path are not loaded; see [`Language::new_from_string`]
+    ///
+    /// # Arguments
+    /// * `reader` - Source of the language's JSON
+    /// * `resources` - Additional resources to embed
+    pub async fn new_from_reader_async<R: AsyncRead + Unpin>(
+        mut reader: R,
+        resources: HashMap<String, Vec<u8>>,
+    ) -> Result<Self, String> {
+        let mut json = String::new();
+        reader
+            .read_to_string(&mut json)
+            .await
+            .map_err(|e| e.to_string())?;
+        Self::new_from_string(&json, resources)
+    }
+
+    /// Read language from a file without blocking
+    /// Resources declared in the file are loaded, relative to the file's directory
+    ///
+    /// # Arguments
+    /// * `path` - Path to the file
+    /// * `resources` - Additional resources to embed
+    pub async fn new_from_file_async(
+        path: &str,
+        resources: HashMap<String, Vec<u8>>,
+    ) -> Result<Self, String> {
+        let file = tokio::fs::File::open(path)
+            .await
+            .map_err(|e| e.to_string())?;
+        let mut lang = Self::new_from_reader_async(file, resources).await?;
+        let directory = Path::new(path).parent().unwrap_or(Path::new(""));
+        for (name, path) in lang.owned_declared_resources() {
+            let bytes = read_file(&resource_path(directory, &path)?).await?;
+            lang.embed_resource(&name, bytes);
+        }
+        Ok(lang)
+    }
+
+    /// Load a language, and the resources it declares, from a source
+    ///
+    /// # Arguments
+    /// * `source` - Where to load the language from
+    /// * `language` - Code of the language
+    pub async fn new_from_source<S: LanguageSource>(
+        source: &S,
+        language: &str,
+    ) -> Result<Self, String> {
+        let json = source.read_language(language).await?;
+        let json = std::str::from_utf8(&json).map_err(|e| e.to_string())?;
+        let mut lang = Self::new_from_string(json, HashMap::default())?;
+        for (name, path) in lang.owned_declared_resources() {
+            let bytes = source.read_resource(language, &path).await?;
+            lang.embed_resource(&name, bytes);
+        }
+        Ok(lang)
+    }
+}
+
+impl LanguageSet {
+    /// Add a language from a JSON file to the set without blocking
+    ///
+    /// # Arguments
+    /// * `filename` - Path to the file
+    /// * `resources` - Additional resources to embed
+    pub async fn load_language_async(
+        &mut self,
+        filename: &str,
+        resources: HashMap<String, Vec<u8>>,
+    ) -> Result<(), String> {
+        let lang = Language::new_from_file_async(filename, resources).await?;
+        self.add_language(lang);
+        Ok(())
+    }
+
+    /// Add every `.lang.json` file in a directory to the set without blocking
+    ///
+    /// # Arguments
+    /// * `path` - Path to the directory
+    pub async fn load_directory_async(&mut self, path: &str) -> Result<(), String> {
+        self.load_source(&DirectorySource::new(path)).await
+    }
+
+    /// Add every language provided by a source to the set
+    ///
+    /// # Arguments
+    /// * `source` - Where to load the languages from
+    pub async fn load_source<S: LanguageSource>(&mut self, source: &S) -> Result<(), String> {
+        for language in source.languages().await? {
+            let lang = Language::new_from_source(source, &language).await?;
+            self.add_language(lang);
+        }
+        Ok(())
+    }
+}
+
+async fn read_file(path: &Path) -> Result<Vec<u8>, String> {
+    tokio::fs::read(path)
+        .await
+        .map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod test_token {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_new_from_file_async() {
+        let lang = Language::new_from_file_async("examples/fr.lang.json", HashMap::default())
+            .await
+            .unwrap();
+        assert_eq!(lang.get("tree"), Some("arbre"));
+        assert!(lang.utf8_resource("welcome").is_some());
+        assert!(
+            Language::new_from_file_async("nope.lang.json", HashMap::default())
+                .await
+                .is_err()
+        );
+
+        let json: &[u8] =
+            br#"{"name": "English", "short_name": "en", "strings": {"tree": "tree"}}"#;
+        let lang = Language::new_from_reader_async(json, HashMap::default())
+            .await
+            .unwrap();
+        assert_eq!(lang.get("tree"), Some("tree"));
+    }
+
+    #[tokio::test]
+    async fn test_load_directory_async() {
+        let mut set = LanguageSet::new("en", &[]);
+        set.load_directory_async("examples").await.unwrap();
+        assert!(set.set_language("fr"));
+        assert_eq!(set.get("tree"), Some("arbre"));
+        assert!(set.utf8_resource("welcome").is_some());
+        assert!(set.load_directory_async("nope").await.is_err());
+
+        let mut set = LanguageSet::new("en", &[]);
+        set.load_language_async("examples/en.lang.json", HashMap::default())
+            .await
+            .unwrap();
+        assert_eq!(set.get("hello_msg"), Some("hello world!"));
+    }
+
+    #[tokio::test]
+    async fn test_memory_source() {
+        let source = MemorySource::new()
+            .with_language(
+                "de",
+                r#"{"name": "Deutsch", "short_name": "de", "strings": {"tree": "Baum"},
+                    "resources": {"logo": "./logo.png"}}"#,
+            )
+            .with_resource("./logo.png", vec![1, 2, 3]);
+        let mut set = LanguageSet::new("de", &[]);
+        set.load_source(&source).await.unwrap();
+        assert_eq!(set.get("tree"), Some("Baum"));
+        assert_eq!(set.binary_resource("logo"), Some(&[1, 2, 3][..]));
+
+        let source = MemorySource::new().with_language(
+            "de",
+            r#"{"name": "Deutsch", "short_name": "de", "strings": {},
+                "resources": {"logo": "./missing.png"}}"#,
+        );
+        assert!(Language::new_from_source(&source, "de").await.is_err());
+        assert!(Language::new_from_source(&source, "fr").await.is_err());
+
+        let directory = DirectorySource::new("examples");
+        assert!(directory
+            .read_resource("fr", "../Cargo.toml")
+            .await
+            .is_err());
+        assert!(directory
+            .read_resource("fr", "/etc/hostname")
+            .await
+            .is_err());
+        assert!(directory
+            .read_resource("fr", "./resources/fr/welcome.txt")
+            .await
+            .is_ok());
+    }
+}
@@ -0,0 +1,439 @@
+//! Command-line tool for managing language files
+//!
+//! Usage:
+//! ```text
+//! embedded-lang verify [--fallback <code>] <file or directory>...
+//! embedded-lang convert [--from <format>] [--to <format>] <input> <output>
+//! embedded-lang diff <old> <new>
+//! embedded-lang stats <file>...
+//! embedded-lang fmt [--check] <file>...
+//! embedded-lang keys [--skeleton <output>] <fallback file> <source file or directory>...
+//! ```
+//!
+//! Formats are `json` for `.lang.json` files, and `flat` for `.flat.json` files
+use embedded_lang::{KeyExtractor, Language, LanguageFormat, LanguageSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+use std::process::ExitCode;
+
+const USAGE: &str = "Usage:
+    embedded-lang verify [--fallback <code>] <file or directory>...
+    embedded-lang convert [--from <format>] [--to <format>] <input> <output>
+    embedded-lang diff <old> <new>
+    embedded-lang stats <file>...
+    embedded-lang fmt [--check] <file>...
+    embedded-lang keys [--skeleton <output>] <fallback file> <source file or directory>...
+
+Formats are `json` for .lang.json files, and `flat` for .flat.json files";
+
+/// Parsed command-line arguments for a subcommand
+#[derive(Debug, Default)]
+struct Arguments {
+    options: HashMap<String, String>,
+    switches: HashSet<String>,
+    positional: Vec<String>,
+}
+
+impl Arguments {
+    /// Split arguments into options taking a value, switches, and positional arguments
+    fn parse(args: &[String], options: &[&str], switches: &[&str]) -> Result<Self, String> {
+        let mut parsed = Self::default();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            if options.contains(&arg.as_str()) {
+                let value = args
+                    .next()
+                    .ok_or_else(|| format!("{} requires a value", arg))?;
+                parsed.options.insert(arg.clone(), value.clone());
+            } else if switches.contains(&arg.as_str()) {
+                parsed.switches.insert(arg.clone());
+            } else if arg.starts_with("--") {
+                return Err(format!("Unknown option {}", arg));
+            } else {
+                parsed.positional.push(arg.clone());
+            }
+        }
+        Ok(parsed)
+    }
+
+    fn format(&self, option: &str, path: &str) -> Result<LanguageFormat, String> {
+        match self.options.get(option) {
+            Some(name) => {
+                LanguageFormat::from_name(name).ok_or_else(|| format!("Unknown format {}", name))
+            }
+            None => Ok(LanguageFormat::from_path(path)),
+        }
+    }
+}
+
+fn read_language(path: &str, format: LanguageFormat) -> Result<Language, String> {
+    let source = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    format.read(&source).map_err(|e| format!("{}: {}", path, e))
+}
+
+/// Check every language against the fallback, listing missing strings and resources, broken constraints and stale translations
+fn verify(args: &Arguments) -> Result<bool, String> {
+    let mut set = LanguageSet::new("", &[]);
+    let mut fallback = args.options.get("--fallback").cloned();
+    for path in &args.positional {
+        if Path::new(path).is_dir() {
+            set.load_directory(path)?;
+        } else {
+            let mut language = read_language(path, LanguageFormat::from_path(path))?;
+            let directory = Path::new(path).parent().unwrap_or(Path::new(""));
+            language
+                .load_declared_resources(directory)
+                .map_err(|e| format!("{}: {}", path, e))?;
+            fallback.get_or_insert_with(|| language.short_name().to_string());
+            set.add_language(language);
+        }
+    }
+
+    let fallback = fallback.ok_or("No fallback language; use --fallback <code>")?;
+    if !set.set_fallback_language(&fallback) {
+        return Err(format!("Fallback language {} was not loaded", fallback));
+    }
+
+    let report: BTreeMap<String, Vec<String>> = set.verify_missing().into_iter().collect();
+    let mut complete = true;
+    for (language, mut missing) in report {
+        missing.sort();
+        println!("{}: {} missing", language, missing.len());
+        for key in &missing {
+            println!("    {}", key);
+        }
+        complete &= missing.is_empty();
+    }
+
+    let report: BTreeMap<String, Vec<String>> = set.verify_resources().into_iter().collect();
+    for (language, mut missing) in report {
+        if missing.is_empty() {
+            continue;
+        }
+
+        missing.sort();
+        println!("{}: {} missing resources", language, missing.len());
+        for name in &missing {
+            println!("    {}", name);
+        }
+        complete = false;
+    }
+
+    let report: BTreeMap<String, Vec<String>> = set.verify_notes().into_iter().collect();
+    for (language, mut problems) in report {
+        if problems.is_empty() {
+            continue;
+        }
+
+        problems.sort();
+        println!("{}: {} problems", language, problems.len());
+        for problem in &problems {
+            println!("    {}", problem);
+        }
+        complete = false;
+    }
+
+    let report: BTreeMap<String, Vec<String>> = set.verify_status().into_iter().collect();
+    for (language, mut stale) in report {
+        if stale.is_empty() {
+            continue;
+        }
+
+        stale.sort();
+        println!("{}: {} stale", language, stale.len());
+        for key in &stale {
+            println!("    {}", key);
+        }
+        complete = false;
+    }
+    Ok(complete)
+}
+
+/// Rewrite a language file in another format
+fn convert(args: &Arguments) -> Result<bool, String> {
+    let [input, output] = args.positional.as_slice() else {
+        return Err("convert requires an input and an output file".to_string());
+    };
+
+    let language = read_language(input, args.format("--from", input)?)?;
+    let contents = args.format("--to", output)?.write(&language)?;
+    std::fs::write(output, contents).map_err(|e| format!("{}: {}", output, e))?;
+    Ok(true)
+}
+
+/// Differences between two versions of a language
+#[derive(Debug, Default, PartialEq)]
+struct Differences {
+    added: Vec<String>,
+    removed: Vec<String>,
+    changed: Vec<(String, String, String)>,
+}
+
+impl Differences {
+    fn new(old: &Language, new: &Language) -> Self {
+        let old_strings: BTreeMap<String, &str> =
+            old.leaves().map(|(p, s)| (p.to_string(), s)).collect();
+        let new_strings: BTreeMap<String, &str> =
+            new.leaves().map(|(p, s)| (p.to_string(), s)).collect();
+
+        let mut differences = Self::default();
+        for (key, old_text) in &old_strings {
+            match new_strings.get(key) {
+                None => differences.removed.push(key.clone()),
+                Some(new_text) if new_text != old_text => differences.changed.push((
+                    key.clone(),
+                    old_text.to_string(),
+                    new_text.to_string(),
+                )),
+                Some(_) => {}
+            }
+        }
+        differences.added = new_strings
+            .keys()
+            .filter(|key| !old_strings.contains_key(*key))
+            .cloned()
+            .collect();
+        differences
+    }
+
+    fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// List the strings added, removed and changed between two language files
+fn diff(args: &Arguments) -> Result<bool, String> {
+    let [old, new] = args.positional.as_slice() else {
+        return Err("diff requires two files".to_string());
+    };
+
+    let old = read_language(old, LanguageFormat::from_path(old))?;
+    let new = read_language(new, LanguageFormat::from_path(new))?;
+    let differences = Differences::new(&old, &new);
+    for key in &differences.removed {
+        println!("- {}", key);
+    }
+    for key in &differences.added {
+        println!("+ {}", key);
+    }
+    for (key, old_text, new_text) in &differences.changed {
+        println!("~ {}: {:?} -> {:?}", key, old_text, new_text);
+    }
+    Ok(differences.is_empty())
+}
+
+/// Counts describing a language
+#[derive(Debug, Default, PartialEq)]
+struct Statistics {
+    strings: usize,
+    categories: usize,
+    words: usize,
+    characters: usize,
+}
+
+impl Statistics {
+    fn new(language: &Language) -> Self {
+        let mut statistics = Self {
+            categories: language.categories().count(),
+            ..Self::default()
+        };
+        for (_, text) in language.leaves() {
+            statistics.strings += 1;
+            statistics.words += text.split_whitespace().count();
+            statistics.characters += text.chars().count();
+        }
+        statistics
+    }
+}
+
+/// Print counts of strings, categories, words and characters in language files
+fn stats(args: &Arguments) -> Result<bool, String> {
+    for path in &args.positional {
+        let language = read_language(path, LanguageFormat::from_path(path))?;
+        let statistics = Statistics::new(&language);
+        println!("{} ({}, {})", path, language.name(), language.short_name());
+        println!("    strings: {}", statistics.strings);
+        println!("    categories: {}", statistics.categories);
+        println!("    words: {}", statistics.words);
+        println!("    characters: {}", statistics.characters);
+    }
+    Ok(true)
+}
+
+/// Rewrite language files with canonical key ordering and indentation
+fn fmt(args: &Arguments) -> Result<bool, String> {
+    let check = args.switches.contains("--check");
+    let mut formatted = true;
+    for path in &args.positional {
+        let format = LanguageFormat::from_path(path);
+        let source = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+        let language = format
+            .read(&source)
+            .map_err(|e| format!("{}: {}", path, e))?;
+        let canonical = format.rewrite(&language, &source)? + "\n";
+
+        if canonical != source {
+            if check {
+                println!("{} is not formatted", path);
+                formatted = false;
+            } else {
+                std::fs::write(path, canonical).map_err(|e| format!("{}: {}", path, e))?;
+            }
+        }
+    }
+    Ok(formatted)
+}
+
+/// Cross-check the keys used by Rust sources against a language file
+fn keys(args: &Arguments) -> Result<bool, String> {
+    let Some((language, sources)) = args.positional.split_first() else {
+        return Err("keys requires a language file".to_string());
+    };
+
+    let language = read_language(language, LanguageFormat::from_path(language))?;
+    let mut extractor = KeyExtractor::new();
+    for path in sources {
+        extractor
+            .scan_path(path)
+            .map_err(|e| format!("{}: {}", path, e))?;
+    }
+
+    let report = extractor.check(&language);
+    println!("used but not defined: {}", report.undefined.len());
+    for key in &report.undefined {
+        println!("    {}", key);
+    }
+    println!("defined but never used: {}", report.unused.len());
+    for key in &report.unused {
+        println!("    {}", key);
+    }
+
+    if let Some(output) = args.options.get("--skeleton") {
+        let (skeleton, conflicts) = extractor.skeleton(&language);
+        for conflict in conflicts {
+            eprintln!("skipped {}", conflict);
+        }
+        let skeleton = LanguageFormat::from_path(output).write(&skeleton)?;
+        std::fs::write(output, skeleton + "\n").map_err(|e| format!("{}: {}", output, e))?;
+    }
+    Ok(report.undefined.is_empty())
+}
+
+fn run(args: &[String]) -> Result<bool, String> {
+    let Some((command, args)) = args.split_first() else {
+        return Err(USAGE.to_string());
+    };
+
+    match command.as_str() {
+        "verify" => verify(&Arguments::parse(args, &["--fallback"], &[])?),
+        "convert" => convert(&Arguments::parse(args, &["--from", "--to"], &[])?),
+        "diff" => diff(&Arguments::parse(args, &[], &[])?),
+        "stats" => stats(&Arguments::parse(args, &[], &[])?),
+        "fmt" => fmt(&Arguments::parse(args, &[], &["--check"])?),
+        "keys" => keys(&Arguments::parse(args, &["--skeleton"], &[])?),
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::from(2)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_token {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        let parsed = Arguments::parse(
+            &args(&["--check", "a", "--fallback", "en", "b"]),
+            &["--fallback"],
+            &["--check"],
+        )
+        .unwrap();
+        assert_eq!(parsed.options["--fallback"], "en");
+        assert!(parsed.switches.contains("--check"));
+        assert_eq!(parsed.positional, vec!["a", "b"]);
+
+        assert!(Arguments::parse(&args(&["--fallback"]), &["--fallback"], &[]).is_err());
+        assert!(Arguments::parse(&args(&["--nope"]), &[], &[]).is_err());
+    }
+
+    #[test]
+    fn test_differences() {
+        let old = read_language("examples/en.lang.json", LanguageFormat::Json).unwrap();
+        let mut new = old.clone();
+        new.set("tree", "oak").unwrap();
+        new.set("leaf", "leaf").unwrap();
+        new.remove("mustard");
+
+        assert_eq!(
+            Differences::new(&old, &new),
+            Differences {
+                added: vec!["leaf".to_string()],
+                removed: vec!["mustard".to_string()],
+                changed: vec![("tree".to_string(), "tree".to_string(), "oak".to_string())],
+            }
+        );
+        assert!(Differences::new(&old, &old).is_empty());
+    }
+
+    #[test]
+    fn test_statistics() {
+        let language = Language::new_from_string(
+            r#"{"name": "English", "short_name": "en", "strings": {"a": "hello world", "b": {"c": "hi"}}}"#,
+            HashMap::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            Statistics::new(&language),
+            Statistics {
+                strings: 2,
+                categories: 1,
+                words: 3,
+                characters: 13,
+            }
+        );
+    }
+
+    #[test]
+    fn test_verify() {
+        let complete = run(&args(&["verify", "--fallback", "en", "examples"])).unwrap();
+        assert!(!complete);
+
+        let complete = run(&args(&[
+            "verify",
+            "examples/fr.lang.json",
+            "examples/en.lang.json",
+        ]));
+        assert_eq!(complete, Ok(false));
+        assert_eq!(
+            complete,
+            run(&args(&["verify", "--fallback", "fr", "examples"]))
+        );
+        assert_eq!(run(&args(&["verify", "examples/fr.lang.json"])), Ok(true));
+
+        assert!(run(&args(&["verify", "examples"])).is_err());
+    }
+
+    #[test]
+    fn test_keys() {
+        let used = run(&args(&["keys", "examples/en.lang.json", "examples"]));
+        assert_eq!(used, Ok(true));
+
+        assert!(run(&args(&["keys"])).is_err());
+    }
+}
@@ -0,0 +1,345 @@
+use std::collections::HashMap;
+
+use crate::number_format::find_locale;
+use crate::NumberFormatter;
+
+/// Built-in data for an ISO 4217 currency
+struct CurrencyData {
+    code: &'static str,
+    digits: usize,
+    symbol: &'static str,
+    narrow: &'static str,
+}
+
+const fn currency(
+    code: &'static str,
+    digits: usize,
+    symbol: &'static str,
+    narrow: &'static str,
+) -> CurrencyData {
+    CurrencyData {
+        code,
+        digits,
+        symbol,
+        narrow,
+    }
+}
+
+/// Common currencies, with their decimal digits and international symbols from the Unicode CLDR
+const CURRENCY_DATA: &[CurrencyData] = &[
+    currency("AUD", 2, "A$", "$"),
+    currency("BHD", 3, "BHD", "BHD"),
+    currency("BRL", 2, "R$", "R$"),
+    currency("CAD", 2, "CA$", "$"),
+    currency("CHF", 2, "CHF", "CHF"),
+    currency("CLP", 0, "CLP", "$"),
+    currency("CNY", 2, "CN¥", "¥"),
+    currency("CZK", 2, "CZK", "Kč"),
+    currency("DKK", 2, "DKK", "kr"),
+    currency("EUR", 2, "€", "€"),
+    currency("GBP", 2, "£", "£"),
+    currency("HKD", 2, "HK$", "$"),
+    currency("HUF", 2, "HUF", "Ft"),
+    currency("IDR", 2, "IDR", "Rp"),
+    currency("ILS", 2, "₪", "₪"),
+    currency("INR", 2, "₹", "₹"),
+    currency("ISK", 0, "ISK", "kr"),
+    currency("JOD", 3, "JOD", "JOD"),
+    currency("JPY", 0, "JP¥", "¥"),
+    currency("KRW", 0, "₩", "₩"),
+    currency("KWD", 3, "KWD", "KWD"),
+    currency("MXN", 2, "MX$", "$"),
+    currency("NOK", 2, "NOK", "kr"),
+    currency("NZD", 2, "NZ$", "$"),
+    currency("PLN", 2, "PLN", "zł"),
+    currency("RUB", 2, "RUB", "₽"),
+    currency("SEK", 2, "SEK", "kr"),
+    currency("SGD", 2, "SGD", "$"),
+    currency("THB", 2, "THB", "฿"),
+    currency("TRY", 2, "TRY", "₺"),
+    currency("TWD", 2, "NT$", "$"),
+    currency("UAH", 2, "UAH", "₴"),
+    currency("USD", 2, "US$", "$"),
+    currency("VND", 0, "₫", "₫"),
+    currency("ZAR", 2, "ZAR", "R"),
+];
+
+/// Where the currency symbol goes in each locale, where `¤` is the symbol and `{0}` the amount
+const CURRENCY_PATTERNS: &[(&str, &str)] = &[
+    ("en", "¤{0}"),
+    ("fr", "{0}\u{A0}¤"),
+    ("de", "{0}\u{A0}¤"),
+    ("de-AT", "¤\u{A0}{0}"),
+    ("de-CH", "¤\u{A0}{0}"),
+    ("es", "{0}\u{A0}¤"),
+    ("es-MX", "¤{0}"),
+    ("es-US", "¤{0}"),
+    ("it", "{0}\u{A0}¤"),
+    ("pt", "¤\u{A0}{0}"),
+    ("pt-PT", "{0}\u{A0}¤"),
+    ("nl", "¤\u{A0}{0}"),
+    ("sv", "{0}\u{A0}¤"),
+    ("nb", "{0}\u{A0}¤"),
+    ("no", "{0}\u{A0}¤"),
+    ("da", "{0}\u{A0}¤"),
+    ("fi", "{0}\u{A0}¤"),
+    ("pl", "{0}\u{A0}¤"),
+    ("ru", "{0}\u{A0}¤"),
+    ("uk", "{0}\u{A0}¤"),
+    ("cs", "{0}\u{A0}¤"),
+    ("hu", "{0}\u{A0}¤"),
+    ("ro", "{0}\u{A0}¤"),
+    ("el", "{0}\u{A0}¤"),
+    ("tr", "¤{0}"),
+    ("he", "{0}\u{A0}¤"),
+    ("ar", "{0}\u{A0}¤"),
+    ("vi", "{0}\u{A0}¤"),
+];
+
+/// Symbols a locale uses instead of the international ones, such as `$` for dollars in English
+const LOCAL_SYMBOLS: &[(&str, &str, &str)] = &[
+    ("en", "USD", "$"),
+    ("en-AU", "AUD", "$"),
+    ("en-AU", "USD", "US$"),
+    ("en-CA", "CAD", "$"),
+    ("en-CA", "USD", "US$"),
+    ("en-NZ", "NZD", "$"),
+    ("en-NZ", "USD", "US$"),
+    ("fr-CA", "CAD", "$"),
+    ("fr-CA", "USD", "$\u{A0}US"),
+    ("es-MX", "MXN", "$"),
+    ("es-MX", "USD", "USD"),
+    ("es-US", "USD", "$"),
+    ("ja", "JPY", "￥"),
+    ("zh", "CNY", "¥"),
+    ("ru", "RUB", "₽"),
+    ("uk", "UAH", "₴"),
+    ("sv", "SEK", "kr"),
+    ("nb", "NOK", "kr"),
+    ("no", "NOK", "kr"),
+    ("da", "DKK", "kr."),
+    ("pl", "PLN", "zł"),
+    ("cs", "CZK", "Kč"),
+    ("hu", "HUF", "Ft"),
+    ("tr", "TRY", "₺"),
+];
+
+/// How a currency is identified next to an amount
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CurrencyDisplay {
+    /// The symbol used in the locale, such as `$` for US dollars in English and `US$` elsewhere
+    Symbol,
+
+    /// The shortest symbol, even if it is ambiguous, such as `$`
+    Narrow,
+
+    /// The ISO 4217 code, such as `USD`
+    Code,
+}
+
+impl CurrencyDisplay {
+    /// Find a display by name; `narrow`, `code`, or anything else for the symbol
+    ///
+    /// # Arguments
+    /// * `name` - Name of the display
+    pub fn from_name(name: &str) -> Self {
+        match name.trim() {
+            "narrow" => Self::Narrow,
+            "code" => Self::Code,
+            _ => Self::Symbol,
+        }
+    }
+}
+
+/// Return the number of decimal digits used by a currency, such as 2 for `EUR` or 0 for `JPY`
+/// Unknown currencies use 2 digits
+///
+/// # Arguments
+/// * `code` - ISO 4217 currency code
+pub fn currency_digits(code: &str) -> usize {
+    find_currency(code).map(|c| c.digits).unwrap_or(2)
+}
+
+fn find_currency(code: &str) -> Option<&'static CurrencyData> {
+    CURRENCY_DATA
+        .iter()
+        .find(|c| c.code.eq_ignore_ascii_case(code))
+}
+
+/// Writes amounts of money using the conventions of a locale
+#[derive(Clone, Debug, PartialEq)]
+pub struct CurrencyFormatter {
+    numbers: NumberFormatter,
+    pattern: String,
+    symbols: HashMap<String, String>,
+}
+
+impl Default for CurrencyFormatter {
+    fn default() -> Self {
+        Self::new("en")
+    }
+}
+
+impl CurrencyFormatter {
+    /// Create a formatter using the built-in conventions of a language
+    ///
+    /// # Arguments
+    /// * `language` - Language code, such as `fr` or `en-CA`
+    pub fn new(language: &str) -> Self {
+        Self::with_numbers(language, NumberFormatter::new(language))
+    }
+
+    /// Create a formatter using the built-in currency conventions of a language, and the given number formatter
+    ///
+    /// # Arguments
+    /// * `language` - Language code, such as `fr` or `en-CA`
+    /// * `numbers` - Number formatter to write amounts with
+    pub fn with_numbers(language: &str, numbers: NumberFormatter) -> Self {
+        let pattern = find_locale(CURRENCY_PATTERNS, language, |p| p.0)
+            .map(|p| p.1)
+            .unwrap_or("¤{0}");
+
+        // Symbols for the primary language first, so the full code can replace them
+        let language = language.replace('_', "-");
+        let primary = language.split('-').next().unwrap_or_default();
+        let mut symbols = HashMap::default();
+        for code in [primary, &language] {
+            symbols.extend(
+                LOCAL_SYMBOLS
+                    .iter()
+                    .filter(|(locale, _, _)| locale.eq_ignore_ascii_case(code))
+                    .map(|(_, currency, symbol)| (currency.to_string(), symbol.to_string())),
+            );
+        }
+
+        Self {
+            numbers,
+            pattern: pattern.to_string(),
+            symbols,
+        }
+    }
+
+    /// Replace the pattern placing the symbol, and the symbols used for some currencies
+    ///
+    /// # Arguments
+    /// * `pattern` - Pattern where `¤` is the symbol and `{0}` the amount, or None to keep the current one
+    /// * `symbols` - Symbols to use, by ISO 4217 code
+    pub fn with_overrides(
+        mut self,
+        pattern: Option<&str>,
+        symbols: &HashMap<String, String>,
+    ) -> Self {
+        if let Some(pattern) = pattern {
+            self.pattern = pattern.to_string();
+        }
+        self.symbols.extend(
+            symbols
+                .iter()
+                .map(|(code, symbol)| (code.to_ascii_uppercase(), symbol.clone())),
+        );
+        self
+    }
+
+    /// Return the symbol written for a currency
+    ///
+    /// # Arguments
+    /// * `code` - ISO 4217 currency code, such as `EUR`
+    /// * `display` - How to identify the currency
+    pub fn symbol(&self, code: &str, display: CurrencyDisplay) -> String {
+        let code = code.to_ascii_uppercase();
+        let data = find_currency(&code);
+        match display {
+            CurrencyDisplay::Code => code,
+            CurrencyDisplay::Narrow => data.map(|c| c.narrow.to_string()).unwrap_or(code),
+            CurrencyDisplay::Symbol => match self.symbols.get(&code) {
+                Some(symbol) => symbol.clone(),
+                None => data.map(|c| c.symbol.to_string()).unwrap_or(code),
+            },
+        }
+    }
+
+    /// Write an amount of money, rounded to the decimal digits of its currency
+    ///
+    /// # Arguments
+    /// * `value` - Amount to write
+    /// * `code` - ISO 4217 currency code, such as `EUR`
+    /// * `display` - How to identify the currency
+    pub fn format(&self, value: f64, code: &str, display: CurrencyDisplay) -> String {
+        let digits = currency_digits(code);
+        let amount = self.numbers.format_fixed(value.abs(), digits, digits);
+        let symbol = self.symbol(code, display);
+
+        // Keep letters in a symbol from running into the digits, as in `CHF 5.00`
+        let mut pattern = self.pattern.clone();
+        if symbol.ends_with(char::is_alphabetic) {
+            pattern = pattern.replace("¤{0}", "¤\u{A0}{0}");
+        }
+        if symbol.starts_with(char::is_alphabetic) {
+            pattern = pattern.replace("{0}¤", "{0}\u{A0}¤");
+        }
+
+        let mut output = String::new();
+        let rounded = format!("{:.*}", digits, value.abs());
+        if value < 0.0 && rounded.chars().any(|c| c.is_ascii_digit() && c != '0') {
+            output.push_str(&self.numbers.symbols().minus);
+        }
+        output.push_str(&pattern.replace('¤', &symbol).replace("{0}", &amount));
+        output
+    }
+}
+
+#[cfg(test)]
+mod test_token {
+    use super::*;
+
+    #[test]
+    fn test_symbol() {
+        let en = CurrencyFormatter::new("en");
+        assert_eq!(en.symbol("usd", CurrencyDisplay::Symbol), "$");
+        assert_eq!(en.symbol("CAD", CurrencyDisplay::Symbol), "CA$");
+        assert_eq!(en.symbol("CAD", CurrencyDisplay::Narrow), "$");
+        assert_eq!(en.symbol("XYZ", CurrencyDisplay::Symbol), "XYZ");
+
+        let en_ca = CurrencyFormatter::new("en-CA");
+        assert_eq!(en_ca.symbol("CAD", CurrencyDisplay::Symbol), "$");
+        assert_eq!(en_ca.symbol("USD", CurrencyDisplay::Symbol), "US$");
+        assert_eq!(currency_digits("JPY"), 0);
+    }
+
+    #[test]
+    fn test_format() {
+        let en = CurrencyFormatter::new("en");
+        assert_eq!(
+            en.format(1234.5, "USD", CurrencyDisplay::Symbol),
+            "$1,234.50"
+        );
+        assert_eq!(en.format(-5.0, "EUR", CurrencyDisplay::Symbol), "-€5.00");
+        assert_eq!(
+            en.format(5.0, "CHF", CurrencyDisplay::Symbol),
+            "CHF\u{A0}5.00"
+        );
+        assert_eq!(
+            en.format(1234.6, "JPY", CurrencyDisplay::Code),
+            "JPY\u{A0}1,235"
+        );
+
+        let de = CurrencyFormatter::new("de");
+        assert_eq!(
+            de.format(1234.5, "EUR", CurrencyDisplay::Symbol),
+            "1.234,50\u{A0}€"
+        );
+        assert_eq!(
+            CurrencyFormatter::new("ja").format(1234.0, "JPY", CurrencyDisplay::Symbol),
+            "￥1,234"
+        );
+
+        let custom = en.with_overrides(
+            Some("{0} ¤"),
+            &HashMap::from([("eur".to_string(), "euros".to_string())]),
+        );
+        assert_eq!(
+            custom.format(2.0, "EUR", CurrencyDisplay::Symbol),
+            "2.00 euros"
+        );
+    }
+}
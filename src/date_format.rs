@@ -0,0 +1,861 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::number_format::find_locale;
+
+/// Built-in date data for a locale
+/// Lists are separated by `|`, and patterns are given from short to full
+struct DateData {
+    code: &'static str,
+    months: &'static str,
+    months_short: &'static str,
+    weekdays: &'static str,
+    weekdays_short: &'static str,
+    day_periods: &'static str,
+    date: [&'static str; 4],
+    time: [&'static str; 4],
+    date_time: &'static str,
+}
+
+const TIME_12: [&str; 4] = ["h:mm a", "h:mm:ss a", "h:mm:ss a", "h:mm:ss a"];
+const TIME_24: [&str; 4] = ["HH:mm", "HH:mm:ss", "HH:mm:ss", "HH:mm:ss"];
+
+/// Date data for common locales, from the Unicode CLDR
+/// Month names use the form found inside a full date, such as the Russian genitive
+const DATE_DATA: &[DateData] = &[
+    DateData {
+        code: "en",
+        months: "January|February|March|April|May|June|July|August|September|October|November|December",
+        months_short: "Jan|Feb|Mar|Apr|May|Jun|Jul|Aug|Sep|Oct|Nov|Dec",
+        weekdays: "Sunday|Monday|Tuesday|Wednesday|Thursday|Friday|Saturday",
+        weekdays_short: "Sun|Mon|Tue|Wed|Thu|Fri|Sat",
+        day_periods: "AM|PM",
+        date: ["M/d/yy", "MMM d, y", "MMMM d, y", "EEEE, MMMM d, y"],
+        time: TIME_12,
+        date_time: "{1}, {0}",
+    },
+    DateData {
+        code: "en-GB",
+        months: "January|February|March|April|May|June|July|August|September|October|November|December",
+        months_short: "Jan|Feb|Mar|Apr|May|Jun|Jul|Aug|Sept|Oct|Nov|Dec",
+        weekdays: "Sunday|Monday|Tuesday|Wednesday|Thursday|Friday|Saturday",
+        weekdays_short: "Sun|Mon|Tue|Wed|Thu|Fri|Sat",
+        day_periods: "am|pm",
+        date: ["dd/MM/y", "d MMM y", "d MMMM y", "EEEE d MMMM y"],
+        time: TIME_24,
+        date_time: "{1}, {0}",
+    },
+    DateData {
+        code: "fr",
+        months: "janvier|février|mars|avril|mai|juin|juillet|août|septembre|octobre|novembre|décembre",
+        months_short: "janv.|févr.|mars|avr.|mai|juin|juil.|août|sept.|oct.|nov.|déc.",
+        weekdays: "dimanche|lundi|mardi|mercredi|jeudi|vendredi|samedi",
+        weekdays_short: "dim.|lun.|mar.|mer.|jeu.|ven.|sam.",
+        day_periods: "AM|PM",
+        date: ["dd/MM/y", "d MMM y", "d MMMM y", "EEEE d MMMM y"],
+        time: TIME_24,
+        date_time: "{1} {0}",
+    },
+    DateData {
+        code: "de",
+        months: "Januar|Februar|März|April|Mai|Juni|Juli|August|September|Oktober|November|Dezember",
+        months_short: "Jan.|Feb.|März|Apr.|Mai|Juni|Juli|Aug.|Sept.|Okt.|Nov.|Dez.",
+        weekdays: "Sonntag|Montag|Dienstag|Mittwoch|Donnerstag|Freitag|Samstag",
+        weekdays_short: "So.|Mo.|Di.|Mi.|Do.|Fr.|Sa.",
+        day_periods: "AM|PM",
+        date: ["dd.MM.yy", "dd.MM.y", "d. MMMM y", "EEEE, d. MMMM y"],
+        time: TIME_24,
+        date_time: "{1}, {0}",
+    },
+    DateData {
+        code: "es",
+        months: "enero|febrero|marzo|abril|mayo|junio|julio|agosto|septiembre|octubre|noviembre|diciembre",
+        months_short: "ene|feb|mar|abr|may|jun|jul|ago|sept|oct|nov|dic",
+        weekdays: "domingo|lunes|martes|miércoles|jueves|viernes|sábado",
+        weekdays_short: "dom|lun|mar|mié|jue|vie|sáb",
+        day_periods: "a.\u{A0}m.|p.\u{A0}m.",
+        date: [
+            "d/M/yy",
+            "d MMM y",
+            "d 'de' MMMM 'de' y",
+            "EEEE, d 'de' MMMM 'de' y",
+        ],
+        time: ["H:mm", "H:mm:ss", "H:mm:ss", "H:mm:ss"],
+        date_time: "{1}, {0}",
+    },
+    DateData {
+        code: "it",
+        months: "gennaio|febbraio|marzo|aprile|maggio|giugno|luglio|agosto|settembre|ottobre|novembre|dicembre",
+        months_short: "gen|feb|mar|apr|mag|giu|lug|ago|set|ott|nov|dic",
+        weekdays: "domenica|lunedì|martedì|mercoledì|giovedì|venerdì|sabato",
+        weekdays_short: "dom|lun|mar|mer|gio|ven|sab",
+        day_periods: "AM|PM",
+        date: ["dd/MM/yy", "d MMM y", "d MMMM y", "EEEE d MMMM y"],
+        time: TIME_24,
+        date_time: "{1}, {0}",
+    },
+    DateData {
+        code: "pt",
+        months: "janeiro|fevereiro|março|abril|maio|junho|julho|agosto|setembro|outubro|novembro|dezembro",
+        months_short: "jan.|fev.|mar.|abr.|mai.|jun.|jul.|ago.|set.|out.|nov.|dez.",
+        weekdays: "domingo|segunda-feira|terça-feira|quarta-feira|quinta-feira|sexta-feira|sábado",
+        weekdays_short: "dom.|seg.|ter.|qua.|qui.|sex.|sáb.",
+        day_periods: "AM|PM",
+        date: [
+            "dd/MM/y",
+            "d 'de' MMM 'de' y",
+            "d 'de' MMMM 'de' y",
+            "EEEE, d 'de' MMMM 'de' y",
+        ],
+        time: TIME_24,
+        date_time: "{1} {0}",
+    },
+    DateData {
+        code: "nl",
+        months: "januari|februari|maart|april|mei|juni|juli|augustus|september|oktober|november|december",
+        months_short: "jan|feb|mrt|apr|mei|jun|jul|aug|sep|okt|nov|dec",
+        weekdays: "zondag|maandag|dinsdag|woensdag|donderdag|vrijdag|zaterdag",
+        weekdays_short: "zo|ma|di|wo|do|vr|za",
+        day_periods: "a.m.|p.m.",
+        date: ["dd-MM-y", "d MMM y", "d MMMM y", "EEEE d MMMM y"],
+        time: TIME_24,
+        date_time: "{1} {0}",
+    },
+    DateData {
+        code: "sv",
+        months: "januari|februari|mars|april|maj|juni|juli|augusti|september|oktober|november|december",
+        months_short: "jan.|feb.|mars|apr.|maj|juni|juli|aug.|sep.|okt.|nov.|dec.",
+        weekdays: "söndag|måndag|tisdag|onsdag|torsdag|fredag|lördag",
+        weekdays_short: "sön|mån|tis|ons|tors|fre|lör",
+        day_periods: "fm|em",
+        date: ["y-MM-dd", "d MMM y", "d MMMM y", "EEEE d MMMM y"],
+        time: TIME_24,
+        date_time: "{1} {0}",
+    },
+    DateData {
+        code: "pl",
+        months: "stycznia|lutego|marca|kwietnia|maja|czerwca|lipca|sierpnia|września|października|listopada|grudnia",
+        months_short: "sty|lut|mar|kwi|maj|cze|lip|sie|wrz|paź|lis|gru",
+        weekdays: "niedziela|poniedziałek|wtorek|środa|czwartek|piątek|sobota",
+        weekdays_short: "niedz.|pon.|wt.|śr.|czw.|pt.|sob.",
+        day_periods: "AM|PM",
+        date: ["d.MM.y", "d MMM y", "d MMMM y", "EEEE, d MMMM y"],
+        time: TIME_24,
+        date_time: "{1}, {0}",
+    },
+    DateData {
+        code: "ru",
+        months: "января|февраля|марта|апреля|мая|июня|июля|августа|сентября|октября|ноября|декабря",
+        months_short: "янв.|февр.|мар.|апр.|мая|июн.|июл.|авг.|сент.|окт.|нояб.|дек.",
+        weekdays: "воскресенье|понедельник|вторник|среда|четверг|пятница|суббота",
+        weekdays_short: "вс|пн|вт|ср|чт|пт|сб",
+        day_periods: "AM|PM",
+        date: [
+            "dd.MM.y",
+            "d MMM y 'г'.",
+            "d MMMM y 'г'.",
+            "EEEE, d MMMM y 'г'.",
+        ],
+        time: TIME_24,
+        date_time: "{1}, {0}",
+    },
+    DateData {
+        code: "ja",
+        months: "1月|2月|3月|4月|5月|6月|7月|8月|9月|10月|11月|12月",
+        months_short: "1月|2月|3月|4月|5月|6月|7月|8月|9月|10月|11月|12月",
+        weekdays: "日曜日|月曜日|火曜日|水曜日|木曜日|金曜日|土曜日",
+        weekdays_short: "日|月|火|水|木|金|土",
+        day_periods: "午前|午後",
+        date: ["y/MM/dd", "y/MM/dd", "y年M月d日", "y年M月d日EEEE"],
+        time: ["H:mm", "H:mm:ss", "H:mm:ss", "H:mm:ss"],
+        date_time: "{1} {0}",
+    },
+    DateData {
+        code: "zh",
+        months: "一月|二月|三月|四月|五月|六月|七月|八月|九月|十月|十一月|十二月",
+        months_short: "1月|2月|3月|4月|5月|6月|7月|8月|9月|10月|11月|12月",
+        weekdays: "星期日|星期一|星期二|星期三|星期四|星期五|星期六",
+        weekdays_short: "周日|周一|周二|周三|周四|周五|周六",
+        day_periods: "上午|下午",
+        date: ["y/M/d", "y年M月d日", "y年M月d日", "y年M月d日EEEE"],
+        time: TIME_24,
+        date_time: "{1} {0}",
+    },
+    DateData {
+        code: "ko",
+        months: "1월|2월|3월|4월|5월|6월|7월|8월|9월|10월|11월|12월",
+        months_short: "1월|2월|3월|4월|5월|6월|7월|8월|9월|10월|11월|12월",
+        weekdays: "일요일|월요일|화요일|수요일|목요일|금요일|토요일",
+        weekdays_short: "일|월|화|수|목|금|토",
+        day_periods: "오전|오후",
+        date: ["yy. M. d.", "y. M. d.", "y년 M월 d일", "y년 M월 d일 EEEE"],
+        time: ["a h:mm", "a h:mm:ss", "a h:mm:ss", "a h:mm:ss"],
+        date_time: "{1} {0}",
+    },
+];
+
+/// Number of days from 1970-01-01 to a date in the proleptic Gregorian calendar
+fn days_from_civil(year: i32, month: u8, day: u8) -> i64 {
+    let year = i64::from(year) - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = i64::from(month);
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// Date in the proleptic Gregorian calendar from a number of days since 1970-01-01
+fn civil_from_days(days: i64) -> (i32, u8, u8) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u8;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year as i32, month, day)
+}
+
+fn days_in_month(year: i32, month: u8) -> u8 {
+    match month {
+        4 | 6 | 9 | 11 => 30,
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        _ => 31,
+    }
+}
+
+/// A date and time of day in the Gregorian calendar, without a time zone
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct DateTime {
+    year: i32,
+    month: u8,
+    day: u8,
+    hour: u8,
+    minute: u8,
+    second: u8,
+}
+
+impl DateTime {
+    /// Create a date at midnight
+    /// Returns None if the date does not exist
+    ///
+    /// # Arguments
+    /// * `year` - Year
+    /// * `month` - Month, from 1 to 12
+    /// * `day` - Day of the month, from 1
+    pub fn new(year: i32, month: u8, day: u8) -> Option<Self> {
+        ((1..=12).contains(&month) && day >= 1 && day <= days_in_month(year, month)).then_some(
+            Self {
+                year,
+                month,
+                day,
+                hour: 0,
+                minute: 0,
+                second: 0,
+            },
+        )
+    }
+
+    /// Set the time of day
+    /// Returns None if the time does not exist
+    ///
+    /// # Arguments
+    /// * `hour` - Hour, from 0 to 23
+    /// * `minute` - Minute, from 0 to 59
+    /// * `second` - Second, from 0 to 59
+    pub fn with_time(self, hour: u8, minute: u8, second: u8) -> Option<Self> {
+        (hour < 24 && minute < 60 && second < 60).then_some(Self {
+            hour,
+            minute,
+            second,
+            ..self
+        })
+    }
+
+    /// Create a date from a number of seconds since 1970-01-01 00:00:00 UTC
+    ///
+    /// # Arguments
+    /// * `timestamp` - Seconds since the unix epoch
+    pub fn from_unix_timestamp(timestamp: i64) -> Self {
+        let (year, month, day) = civil_from_days(timestamp.div_euclid(86400));
+        let seconds = timestamp.rem_euclid(86400);
+        Self {
+            year,
+            month,
+            day,
+            hour: (seconds / 3600) as u8,
+            minute: (seconds / 60 % 60) as u8,
+            second: (seconds % 60) as u8,
+        }
+    }
+
+    /// Return the current date and time in UTC
+    pub fn now() -> Self {
+        Self::from(SystemTime::now())
+    }
+
+    /// Return the number of seconds since 1970-01-01 00:00:00 UTC
+    pub fn unix_timestamp(&self) -> i64 {
+        days_from_civil(self.year, self.month, self.day) * 86400
+            + i64::from(self.hour) * 3600
+            + i64::from(self.minute) * 60
+            + i64::from(self.second)
+    }
+
+    /// Return the year
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// Return the month, from 1 to 12
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    /// Return the day of the month, from 1
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// Return the hour, from 0 to 23
+    pub fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    /// Return the minute, from 0 to 59
+    pub fn minute(&self) -> u8 {
+        self.minute
+    }
+
+    /// Return the second, from 0 to 59
+    pub fn second(&self) -> u8 {
+        self.second
+    }
+
+    /// Return the day of the week, from 0 for Sunday to 6 for Saturday
+    pub fn weekday(&self) -> u8 {
+        (days_from_civil(self.year, self.month, self.day) + 4).rem_euclid(7) as u8
+    }
+}
+
+impl From<SystemTime> for DateTime {
+    fn from(time: SystemTime) -> Self {
+        match time.duration_since(UNIX_EPOCH) {
+            Ok(elapsed) => Self::from_unix_timestamp(elapsed.as_secs() as i64),
+            Err(e) => Self::from_unix_timestamp(-(e.duration().as_secs_f64().ceil() as i64)),
+        }
+    }
+}
+
+/// How much detail a date or time is written with
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum DateLength {
+    /// Numeric, such as 1/2/25
+    Short,
+
+    /// Abbreviated, such as Jan 2, 2025
+    Medium,
+
+    /// Written out, such as January 2, 2025
+    Long,
+
+    /// Written out with the day of the week, such as Thursday, January 2, 2025
+    Full,
+}
+
+impl DateLength {
+    /// Find a length by name; `short`, `medium`, `long` or `full`
+    ///
+    /// # Arguments
+    /// * `name` - Name of the length
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim() {
+            "short" => Some(Self::Short),
+            "medium" => Some(Self::Medium),
+            "long" => Some(Self::Long),
+            "full" => Some(Self::Full),
+            _ => None,
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Names and patterns used to write dates in a locale
+///
+/// Patterns use CLDR letters: `y`, `yy` for years, `M` to `MMMM` for months, `d`, `dd` for days,
+/// `E` to `EEEE` for days of the week, `H`, `h`, `m`, `s` for the time of day and `a` for AM or PM.
+/// Text between single quotes is written as-is.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct DateSymbols {
+    /// Names of the months, from January
+    pub months: Vec<String>,
+
+    /// Abbreviated names of the months, from January
+    pub months_short: Vec<String>,
+
+    /// Names of the days of the week, from Sunday
+    pub weekdays: Vec<String>,
+
+    /// Abbreviated names of the days of the week, from Sunday
+    pub weekdays_short: Vec<String>,
+
+    /// Names for before and after noon
+    pub day_periods: Vec<String>,
+
+    /// Date patterns, from short to full
+    pub date_patterns: Vec<String>,
+
+    /// Time patterns, from short to full
+    pub time_patterns: Vec<String>,
+
+    /// Pattern combining a date and time, where `{1}` is the date and `{0}` the time
+    pub date_time_pattern: String,
+}
+
+impl Default for DateSymbols {
+    fn default() -> Self {
+        Self::for_language("en")
+    }
+}
+
+impl DateSymbols {
+    /// Get the built-in date symbols for a language code
+    /// Unknown languages use English conventions
+    ///
+    /// # Arguments
+    /// * `language` - Language code, such as `fr` or `en-GB`
+    pub fn for_language(language: &str) -> Self {
+        let data = find_locale(DATE_DATA, language, |d| d.code).unwrap_or(&DATE_DATA[0]);
+        let list = |s: &str| s.split('|').map(str::to_string).collect();
+        Self {
+            months: list(data.months),
+            months_short: list(data.months_short),
+            weekdays: list(data.weekdays),
+            weekdays_short: list(data.weekdays_short),
+            day_periods: list(data.day_periods),
+            date_patterns: data.date.iter().map(|p| p.to_string()).collect(),
+            time_patterns: data.time.iter().map(|p| p.to_string()).collect(),
+            date_time_pattern: data.date_time.to_string(),
+        }
+    }
+
+    /// Replace the symbols set in a language file
+    /// Lists of names with the wrong length are ignored; see [`DateOverrides::validate`]
+    ///
+    /// # Arguments
+    /// * `overrides` - Symbols to replace
+    pub fn with_overrides(mut self, overrides: &DateOverrides) -> Self {
+        let replace = |target: &mut Vec<String>, source: &Option<Vec<String>>| {
+            if let Some(source) = source.as_ref().filter(|s| s.len() == target.len()) {
+                *target = source.clone();
+            }
+        };
+        replace(&mut self.months, &overrides.months);
+        replace(&mut self.months_short, &overrides.months_short);
+        replace(&mut self.weekdays, &overrides.weekdays);
+        replace(&mut self.weekdays_short, &overrides.weekdays_short);
+        replace(&mut self.day_periods, &overrides.day_periods);
+
+        for (length, pattern) in &overrides.date_patterns {
+            set_pattern(
+                &mut self.date_patterns,
+                &DATE_DATA[0].date,
+                *length,
+                pattern,
+            );
+        }
+        for (length, pattern) in &overrides.time_patterns {
+            set_pattern(
+                &mut self.time_patterns,
+                &DATE_DATA[0].time,
+                *length,
+                pattern,
+            );
+        }
+        if let Some(pattern) = &overrides.date_time_pattern {
+            self.date_time_pattern = pattern.clone();
+        }
+        self
+    }
+
+    /// Return the date pattern for a length, or the English one if there is none
+    fn date_pattern(&self, length: DateLength) -> &str {
+        self.date_patterns
+            .get(length.index())
+            .map_or(DATE_DATA[0].date[length.index()], String::as_str)
+    }
+
+    /// Return the time pattern for a length, or the English one if there is none
+    fn time_pattern(&self, length: DateLength) -> &str {
+        self.time_patterns
+            .get(length.index())
+            .map_or(DATE_DATA[0].time[length.index()], String::as_str)
+    }
+}
+
+/// Replace the pattern for a length, filling a list that is too short with English patterns
+fn set_pattern(
+    patterns: &mut Vec<String>,
+    defaults: &[&str; 4],
+    length: DateLength,
+    pattern: &str,
+) {
+    let index = length.index();
+    if patterns.len() <= index {
+        patterns.extend(
+            defaults[patterns.len()..=index]
+                .iter()
+                .map(|p| p.to_string()),
+        );
+    }
+    patterns[index] = pattern.to_string();
+}
+
+/// Date symbols customized by a language file
+/// Anything left out uses the built-in data for the language
+#[derive(Serialize, Deserialize, Clone, Debug, Default, Eq, PartialEq)]
+pub struct DateOverrides {
+    /// Names of the months, from January
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub months: Option<Vec<String>>,
+
+    /// Abbreviated names of the months, from January
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub months_short: Option<Vec<String>>,
+
+    /// Names of the days of the week, from Sunday
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weekdays: Option<Vec<String>>,
+
+    /// Abbreviated names of the days of the week, from Sunday
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weekdays_short: Option<Vec<String>>,
+
+    /// Names for before and after noon
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub day_periods: Option<Vec<String>>,
+
+    /// Date patterns, by length
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub date_patterns: HashMap<DateLength, String>,
+
+    /// Time patterns, by length
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub time_patterns: HashMap<DateLength, String>,
+
+    /// Pattern combining a date and time, where `{1}` is the date and `{0}` the time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date_time_pattern: Option<String>,
+}
+
+impl DateOverrides {
+    /// Return true if nothing is customized
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Check that every list has the right number of names
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, list, len) in [
+            ("months", &self.months, 12),
+            ("months_short", &self.months_short, 12),
+            ("weekdays", &self.weekdays, 7),
+            ("weekdays_short", &self.weekdays_short, 7),
+            ("day_periods", &self.day_periods, 2),
+        ] {
+            if let Some(list) = list {
+                if list.len() != len {
+                    return Err(format!(
+                        "{} has {} names, but should have {}",
+                        name,
+                        list.len(),
+                        len
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes dates and times using the conventions of a locale
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DateFormatter {
+    symbols: DateSymbols,
+}
+
+impl DateFormatter {
+    /// Create a formatter using the built-in conventions of a language
+    ///
+    /// # Arguments
+    /// * `language` - Language code, such as `fr` or `en-GB`
+    pub fn new(language: &str) -> Self {
+        Self::with_symbols(DateSymbols::for_language(language))
+    }
+
+    /// Create a formatter using the given conventions
+    ///
+    /// # Arguments
+    /// * `symbols` - Date symbols to use
+    pub fn with_symbols(symbols: DateSymbols) -> Self {
+        Self { symbols }
+    }
+
+    /// Return the conventions used by this formatter
+    pub fn symbols(&self) -> &DateSymbols {
+        &self.symbols
+    }
+
+    /// Write the date part of a date
+    ///
+    /// # Arguments
+    /// * `date` - Date to write
+    /// * `length` - How much detail to write
+    pub fn format_date(&self, date: &DateTime, length: DateLength) -> String {
+        self.format_pattern(date, self.symbols.date_pattern(length))
+    }
+
+    /// Write the time part of a date
+    ///
+    /// # Arguments
+    /// * `date` - Date to write
+    /// * `length` - How much detail to write
+    pub fn format_time(&self, date: &DateTime, length: DateLength) -> String {
+        self.format_pattern(date, self.symbols.time_pattern(length))
+    }
+
+    /// Write a date and its time of day
+    ///
+    /// # Arguments
+    /// * `date` - Date to write
+    /// * `length` - How much detail to write
+    pub fn format_datetime(&self, date: &DateTime, length: DateLength) -> String {
+        self.symbols
+            .date_time_pattern
+            .replace("{0}", &self.format_time(date, length))
+            .replace("{1}", &self.format_date(date, length))
+    }
+
+    /// Write a date using a CLDR pattern, such as `d MMMM y`
+    ///
+    /// # Arguments
+    /// * `date` - Date to write
+    /// * `pattern` - Pattern to follow
+    pub fn format_pattern(&self, date: &DateTime, pattern: &str) -> String {
+        let mut output = String::new();
+        let mut chars = pattern.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c == '\'' {
+                if chars.peek() == Some(&'\'') {
+                    chars.next();
+                    output.push('\'');
+                    continue;
+                }
+                while let Some(c) = chars.next() {
+                    match c {
+                        '\'' if chars.peek() == Some(&'\'') => {
+                            chars.next();
+                            output.push('\'');
+                        }
+                        '\'' => break,
+                        _ => output.push(c),
+                    }
+                }
+            } else if c.is_ascii_alphabetic() {
+                let mut count = 1;
+                while chars.peek() == Some(&c) {
+                    chars.next();
+                    count += 1;
+                }
+                self.format_field(&mut output, date, c, count);
+            } else {
+                output.push(c);
+            }
+        }
+        output
+    }
+
+    /// Write a single pattern field, such as `MMMM`
+    fn format_field(&self, output: &mut String, date: &DateTime, field: char, count: usize) {
+        let symbols = &self.symbols;
+        let number = |n: i64| format!("{:0width$}", n, width = count.min(2));
+        // Symbols can be replaced with lists of any length, so missing names are written as numbers
+        let name = |names: &[String], index: usize, n: i64| {
+            names.get(index).cloned().unwrap_or_else(|| number(n))
+        };
+        let month = usize::from(date.month).wrapping_sub(1);
+        let weekday = date.weekday() as usize;
+        let text = match field {
+            'y' if count == 2 => format!("{:02}", date.year.rem_euclid(100)),
+            'y' => format!("{:0width$}", date.year, width = count),
+            'M' | 'L' => match count {
+                1 | 2 => number(date.month.into()),
+                3 => name(&symbols.months_short, month, date.month.into()),
+                _ => name(&symbols.months, month, date.month.into()),
+            },
+            'd' => number(date.day.into()),
+            'E' | 'c' => match count {
+                1..=3 => name(&symbols.weekdays_short, weekday, weekday as i64),
+                _ => name(&symbols.weekdays, weekday, weekday as i64),
+            },
+            'a' => match symbols.day_periods.get(usize::from(date.hour >= 12)) {
+                Some(period) => period.clone(),
+                None if date.hour >= 12 => "PM".to_string(),
+                None => "AM".to_string(),
+            },
+            'H' => number(date.hour.into()),
+            'k' => number(if date.hour == 0 { 24 } else { date.hour.into() }),
+            'K' => number((date.hour % 12).into()),
+            'h' => number(match date.hour % 12 {
+                0 => 12,
+                hour => hour.into(),
+            }),
+            'm' => number(date.minute.into()),
+            's' => number(date.second.into()),
+            _ => std::iter::repeat_n(field, count).collect(),
+        };
+        output.push_str(&text);
+    }
+}
+
+#[cfg(test)]
+mod test_token {
+    use super::*;
+
+    #[test]
+    fn test_date_time() {
+        let date = DateTime::new(2025, 1, 2)
+            .unwrap()
+            .with_time(15, 4, 5)
+            .unwrap();
+        assert_eq!(date.weekday(), 4);
+        assert_eq!(date.unix_timestamp(), 1735830245);
+        assert_eq!(DateTime::from_unix_timestamp(1735830245), date);
+        assert_eq!(
+            DateTime::from_unix_timestamp(-1),
+            DateTime::new(1969, 12, 31)
+                .unwrap()
+                .with_time(23, 59, 59)
+                .unwrap()
+        );
+
+        assert!(DateTime::new(2024, 2, 29).is_some());
+        assert!(DateTime::new(2025, 2, 29).is_none());
+        assert!(DateTime::new(2025, 13, 1).is_none());
+        assert!(date.with_time(24, 0, 0).is_none());
+    }
+
+    #[test]
+    fn test_format() {
+        let date = DateTime::new(2025, 1, 2)
+            .unwrap()
+            .with_time(15, 4, 5)
+            .unwrap();
+
+        let en = DateFormatter::new("en");
+        assert_eq!(en.format_date(&date, DateLength::Short), "1/2/25");
+        assert_eq!(
+            en.format_date(&date, DateLength::Full),
+            "Thursday, January 2, 2025"
+        );
+        assert_eq!(en.format_time(&date, DateLength::Short), "3:04 PM");
+        assert_eq!(
+            en.format_datetime(&date, DateLength::Medium),
+            "Jan 2, 2025, 3:04:05 PM"
+        );
+
+        let fr = DateFormatter::new("fr-CA");
+        assert_eq!(
+            fr.format_date(&date, DateLength::Full),
+            "jeudi 2 janvier 2025"
+        );
+        assert_eq!(fr.format_time(&date, DateLength::Short), "15:04");
+
+        let ru = DateFormatter::new("ru");
+        assert_eq!(ru.format_date(&date, DateLength::Long), "2 января 2025 г.");
+        assert_eq!(
+            en.format_pattern(&date, "yyyy-MM-dd'T'HH:mm 'o''clock'"),
+            "2025-01-02T15:04 o'clock"
+        );
+    }
+
+    #[test]
+    fn test_overrides() {
+        let overrides: DateOverrides = serde_json::from_str(
+            r#"{"date_patterns": {"short": "y-MM-dd"}, "day_periods": ["a.m.", "p.m."]}"#,
+        )
+        .unwrap();
+        assert!(overrides.validate().is_ok());
+
+        let en =
+            DateFormatter::with_symbols(DateSymbols::for_language("en").with_overrides(&overrides));
+        let date = DateTime::new(2025, 1, 2)
+            .unwrap()
+            .with_time(9, 30, 0)
+            .unwrap();
+        assert_eq!(en.format_date(&date, DateLength::Short), "2025-01-02");
+        assert_eq!(en.format_date(&date, DateLength::Long), "January 2, 2025");
+        assert_eq!(en.format_time(&date, DateLength::Short), "9:30 a.m.");
+
+        let overrides = DateOverrides {
+            weekdays: Some(vec!["Sunday".to_string()]),
+            ..Default::default()
+        };
+        assert!(overrides.validate().is_err());
+        let en =
+            DateFormatter::with_symbols(DateSymbols::for_language("en").with_overrides(&overrides));
+        assert_eq!(
+            en.format_date(&date, DateLength::Full),
+            "Thursday, January 2, 2025"
+        );
+    }
+
+    #[test]
+    fn test_missing_symbols() {
+        let mut symbols = DateSymbols::for_language("en");
+        symbols.months.clear();
+        symbols.weekdays_short.truncate(1);
+        symbols.day_periods.clear();
+        let formatter = DateFormatter::with_symbols(symbols);
+        let date = DateTime::new(2025, 3, 6)
+            .unwrap()
+            .with_time(15, 4, 5)
+            .unwrap();
+        assert_eq!(
+            formatter.format_pattern(&date, "MMMM d, E h:mm a"),
+            "03 6, 4 3:04 PM"
+        );
+
+        let mut symbols = DateSymbols::for_language("fr");
+        symbols.date_patterns.clear();
+        symbols.time_patterns.truncate(1);
+        let formatter = DateFormatter::with_symbols(symbols.clone());
+        assert_eq!(
+            formatter.format_date(&date, DateLength::Medium),
+            "mars 6, 2025"
+        );
+        assert_eq!(formatter.format_time(&date, DateLength::Short), "15:04");
+        assert_eq!(formatter.format_time(&date, DateLength::Long), "3:04:05 PM");
+
+        let overrides = DateOverrides {
+            date_patterns: HashMap::from([(DateLength::Long, "d MMMM y".to_string())]),
+            ..Default::default()
+        };
+        let symbols = symbols.with_overrides(&overrides);
+        assert_eq!(symbols.date_patterns.len(), 3);
+        assert_eq!(
+            DateFormatter::with_symbols(symbols).format_date(&date, DateLength::Long),
+            "6 mars 2025"
+        );
+    }
+}
@@ -0,0 +1,444 @@
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+
+use crate::Language;
+
+/// A token of Rust source, as far as key extraction is concerned
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Ident(String),
+    Punct(char),
+    Str(String),
+
+    /// Byte strings, char literals and numbers, which can never be keys
+    Other,
+}
+
+/// Split Rust source into tokens, skipping whitespace and comments
+fn tokenize(source: &str) -> Vec<Token> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '/' && chars.get(i + 1) == Some(&'/') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+        } else if c == '/' && chars.get(i + 1) == Some(&'*') {
+            let mut depth = 0;
+            while i < chars.len() {
+                if chars[i] == '/' && chars.get(i + 1) == Some(&'*') {
+                    depth += 1;
+                    i += 2;
+                } else if chars[i] == '*' && chars.get(i + 1) == Some(&'/') {
+                    depth -= 1;
+                    i += 2;
+                    if depth == 0 {
+                        break;
+                    }
+                } else {
+                    i += 1;
+                }
+            }
+        } else if c == '"' {
+            let (text, end) = read_string(&chars, i + 1);
+            tokens.push(Token::Str(text));
+            i = end;
+        } else if c == '\'' {
+            // Either a char literal, or a lifetime
+            if chars.get(i + 1) == Some(&'\\') {
+                i += 2;
+                while i < chars.len() && chars[i] != '\'' {
+                    i += 1;
+                }
+                i += 1;
+                tokens.push(Token::Other);
+            } else if chars.get(i + 2) == Some(&'\'') {
+                i += 3;
+                tokens.push(Token::Other);
+            } else {
+                i += 1;
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+            }
+        } else if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let ident: String = chars[start..i].iter().collect();
+
+            match (ident.as_str(), chars.get(i)) {
+                ("r" | "br", Some('"' | '#')) if is_raw_string(&chars, i) => {
+                    let (text, end) = read_raw_string(&chars, i);
+                    tokens.push(if ident == "r" {
+                        Token::Str(text)
+                    } else {
+                        Token::Other
+                    });
+                    i = end;
+                }
+                ("b", Some('"')) => {
+                    i = read_string(&chars, i + 1).1;
+                    tokens.push(Token::Other);
+                }
+                _ => tokens.push(Token::Ident(ident)),
+            }
+        } else if c.is_ascii_digit() {
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push(Token::Other);
+        } else {
+            tokens.push(Token::Punct(c));
+            i += 1;
+        }
+    }
+    tokens
+}
+
+/// Read the body of a string literal starting after its opening quote
+/// Returns the unescaped text, and the position after the closing quote
+fn read_string(chars: &[char], mut i: usize) -> (String, usize) {
+    let mut text = String::new();
+    while i < chars.len() && chars[i] != '"' {
+        if chars[i] == '\\' {
+            i += 1;
+            match chars.get(i) {
+                Some('n') => text.push('\n'),
+                Some('t') => text.push('\t'),
+                Some('r') => text.push('\r'),
+                Some('0') => text.push('\0'),
+                Some('u') => {
+                    // Escapes without a closing brace are skipped
+                    let end = (i..chars.len()).find(|j| matches!(chars[*j], '}' | '"'));
+                    if let Some(end) = end.filter(|end| chars[*end] == '}') {
+                        let hex: String = chars[i + 1..end]
+                            .iter()
+                            .filter(|c| c.is_ascii_hexdigit())
+                            .collect();
+                        if let Some(c) = u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32)
+                        {
+                            text.push(c);
+                        }
+                        i = end;
+                    }
+                }
+                Some('\n') => {
+                    while chars.get(i + 1).is_some_and(|c| c.is_whitespace()) {
+                        i += 1;
+                    }
+                }
+                Some(c) => text.push(*c),
+                None => {}
+            }
+        } else {
+            text.push(chars[i]);
+        }
+        i += 1;
+    }
+    (text, i + 1)
+}
+
+/// Return true if a run of `#` followed by a quote starts at a position, as in `r#"raw"#`
+/// Raw identifiers, such as `r#type`, are not raw strings
+fn is_raw_string(chars: &[char], i: usize) -> bool {
+    chars[i..].iter().find(|c| **c != '#') == Some(&'"')
+}
+
+/// Read a raw string literal starting at its first `#` or quote
+/// Returns the text, and the position after the closing delimiter
+fn read_raw_string(chars: &[char], mut i: usize) -> (String, usize) {
+    let hashes = chars[i..].iter().take_while(|c| **c == '#').count();
+    i += hashes + 1;
+
+    let start = i;
+    while i < chars.len() {
+        if chars[i] == '"'
+            && chars[i + 1..]
+                .iter()
+                .take(hashes)
+                .filter(|c| **c == '#')
+                .count()
+                == hashes
+        {
+            return (chars[start..i].iter().collect(), i + 1 + hashes);
+        }
+        i += 1;
+    }
+    (chars[start..].iter().collect(), i)
+}
+
+/// Find the string literal passed as an argument of a call
+/// `position` is the index of the argument, and `open` the index of the opening parenthesis
+fn literal_argument(tokens: &[Token], open: usize, position: usize) -> Option<&str> {
+    let mut depth = 0;
+    let mut argument = 0;
+    let mut start = open + 1;
+    for (i, token) in tokens.iter().enumerate().skip(open + 1) {
+        match token {
+            Token::Punct('(' | '[' | '{') => depth += 1,
+            Token::Punct(')' | ']' | '}') if depth > 0 => depth -= 1,
+            Token::Punct(',' | ')') if depth == 0 => {
+                if argument == position {
+                    return match &tokens[start..i] {
+                        [Token::Str(s)] => Some(s),
+                        _ => None,
+                    };
+                }
+                if tokens[i] == Token::Punct(')') {
+                    return None;
+                }
+                argument += 1;
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Collects the string keys referenced by Rust source code
+///
+/// Recognizes literal keys passed to `get_string!`, `lookup_string!`, `get`, `lookup` and `get_from_lang`, and
+/// used with the index operator. The scan is purely syntactic, so a literal passed to an
+/// unrelated `get` method, or used to index an unrelated map, is reported as well
+#[derive(Clone, Debug, Default)]
+pub struct KeyExtractor {
+    keys: BTreeSet<String>,
+}
+
+impl KeyExtractor {
+    /// Create a new extractor with no keys found
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return every key found so far, in sorted order
+    pub fn keys(&self) -> &BTreeSet<String> {
+        &self.keys
+    }
+
+    /// Find the keys referenced by a piece of Rust source
+    ///
+    /// # Arguments
+    /// * `source` - Rust source code
+    pub fn scan_source(&mut self, source: &str) {
+        let tokens = tokenize(source);
+        for (i, token) in tokens.iter().enumerate() {
+            let key = match (token, tokens.get(i + 1), tokens.get(i + 2)) {
+                (Token::Ident(name), Some(Token::Punct('!')), Some(Token::Punct('(')))
+                    if name == "get_string" || name == "lookup_string" =>
+                {
+                    literal_argument(&tokens, i + 2, 1)
+                }
+                (Token::Punct('.'), Some(Token::Ident(name)), Some(Token::Punct('('))) => {
+                    match name.as_str() {
+                        "get" | "lookup" => literal_argument(&tokens, i + 2, 0),
+                        "get_from_lang" => literal_argument(&tokens, i + 2, 1),
+                        _ => None,
+                    }
+                }
+                (
+                    Token::Ident(_) | Token::Punct(')' | ']'),
+                    Some(Token::Punct('[')),
+                    Some(Token::Str(s)),
+                ) if tokens.get(i + 3) == Some(&Token::Punct(']')) => Some(s.as_str()),
+                _ => None,
+            };
+
+            if let Some(key) = key {
+                self.keys.insert(key.to_string());
+            }
+        }
+    }
+
+    /// Find the keys referenced by a Rust source file, or every `.rs` file in a directory
+    ///
+    /// # Arguments
+    /// * `path` - Path to the file or directory
+    pub fn scan_path(&mut self, path: &str) -> Result<(), String> {
+        let path = Path::new(path);
+        if path.is_dir() {
+            let mut entries = std::fs::read_dir(path)
+                .map_err(|e| e.to_string())?
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .collect::<Vec<_>>();
+            entries.sort();
+
+            for entry in entries {
+                if entry.is_dir() || entry.extension().is_some_and(|e| e == "rs") {
+                    self.scan_path(&entry.to_string_lossy())?;
+                }
+            }
+            Ok(())
+        } else {
+            let source = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+            self.scan_source(&source);
+            Ok(())
+        }
+    }
+
+    /// Cross-check the keys found against a language, usually the fallback language
+    /// Keys in a namespace, such as `plugin_x:tree`, belong to another set and are not checked
+    ///
+    /// # Arguments
+    /// * `language` - Language defining the available strings
+    pub fn check(&self, language: &Language) -> KeyUsageReport {
+        let defined: BTreeSet<String> = language.leaves().map(|(p, _)| p.to_string()).collect();
+        KeyUsageReport {
+            undefined: self
+                .keys
+                .difference(&defined)
+                .filter(|key| !key.contains(':'))
+                .cloned()
+                .collect(),
+            unused: defined.difference(&self.keys).cloned().collect(),
+        }
+    }
+
+    /// Build a skeleton language defining every key found that is missing from a language
+    /// Each string is set to its own key, ready to be translated
+    /// Returns the skeleton, and an error for each key that conflicts with another, such as
+    /// a key used both as a string and as a category
+    ///
+    /// # Arguments
+    /// * `language` - Language defining the available strings
+    pub fn skeleton(&self, language: &Language) -> (Language, Vec<String>) {
+        let mut skeleton = Language::new(
+            language.name().to_string(),
+            language.short_name().to_string(),
+            HashMap::default(),
+            HashMap::default(),
+        );
+        let mut conflicts = vec![];
+        for key in self.check(language).undefined {
+            if let Err(e) = skeleton.set(&key, &key) {
+                conflicts.push(format!("{}: {}", key, e));
+            }
+        }
+        (skeleton, conflicts)
+    }
+}
+
+/// Result of cross-checking the keys used by source code against a language
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct KeyUsageReport {
+    /// Keys used by the source, but not defined by the language
+    pub undefined: Vec<String>,
+
+    /// Keys defined by the language, but never used by the source
+    pub unused: Vec<String>,
+}
+
+#[cfg(test)]
+mod test_token {
+    use super::*;
+    use crate as embedded_lang;
+    use crate::embedded_language;
+
+    const SOURCE: &str = r##"
+        fn main() {
+            // get_string!(set, "commented")
+            let a = get_string!(translator, "tree");
+            let b = translator.get("category\\category2\\foo").unwrap();
+            let c = set.get_from_lang(&lang, r#"quoted "key""#);
+            let d = &set["index"];
+            let e = set.lookup("plugin_x:tree");
+            let f = vec!["not_a_key"];
+            let g = set.get(name);
+            let h = set.get(b"bytes");
+            let i: &'static str = &set[format!("{}", 'x')];
+            /* set.get("block") */
+        }
+    "##;
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            tokenize(r#"a.get("x\ty") 'c' b"b" r"raw""#),
+            vec![
+                Token::Ident("a".to_string()),
+                Token::Punct('.'),
+                Token::Ident("get".to_string()),
+                Token::Punct('('),
+                Token::Str("x\ty".to_string()),
+                Token::Punct(')'),
+                Token::Other,
+                Token::Other,
+                Token::Str("raw".to_string()),
+            ]
+        );
+        assert_eq!(
+            tokenize(r#""\u{e9}" "\u{e9" x"#),
+            vec![
+                Token::Str("\u{e9}".to_string()),
+                Token::Str("{e9".to_string()),
+                Token::Ident("x".to_string()),
+            ]
+        );
+        assert_eq!(tokenize(r#""\u{e9"#), vec![Token::Str("{e9".to_string())]);
+    }
+
+    #[test]
+    fn test_scan_source() {
+        let mut extractor = KeyExtractor::new();
+        extractor.scan_source(SOURCE);
+
+        let keys: Vec<&str> = extractor.keys().iter().map(|k| k.as_str()).collect();
+        assert_eq!(
+            keys,
+            vec![
+                "category\\category2\\foo",
+                "index",
+                "plugin_x:tree",
+                "quoted \"key\"",
+                "tree"
+            ]
+        );
+
+        let mut extractor = KeyExtractor::new();
+        extractor.scan_source(r#"lookup_string!(set, "looked_up")"#);
+        assert!(extractor.keys().contains("looked_up"));
+
+        let mut extractor = KeyExtractor::new();
+        extractor.scan_source(r#"let r#type = 1; get_string!(set, "after_raw_ident");"#);
+        assert!(extractor.keys().contains("after_raw_ident"));
+    }
+
+    #[test]
+    fn test_scan_path() {
+        let mut extractor = KeyExtractor::new();
+        extractor.scan_path("examples").unwrap();
+        assert!(extractor.keys().contains("tree"));
+        assert!(extractor.scan_path("nope").is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        let mut extractor = KeyExtractor::new();
+        extractor.scan_source(SOURCE);
+        let lang = embedded_language!("../examples/en.lang.json");
+
+        let report = extractor.check(&lang);
+        assert_eq!(report.undefined, vec!["index", "quoted \"key\""]);
+        assert_eq!(report.unused, vec!["hello_msg", "mustard"]);
+
+        let (skeleton, conflicts) = extractor.skeleton(&lang);
+        assert_eq!(skeleton.short_name(), "en");
+        assert_eq!(skeleton.get("index"), Some("index"));
+        assert_eq!(skeleton.get("plugin_x:tree"), None);
+        assert_eq!(skeleton.leaves().count(), 2);
+        assert!(conflicts.is_empty());
+
+        extractor.scan_source(r#"set.get("index\\nested"); set.get("other");"#);
+        let (skeleton, conflicts) = extractor.skeleton(&lang);
+        assert_eq!(skeleton.get("other"), Some("other"));
+        assert_eq!(conflicts.len(), 1);
+        assert!(conflicts[0].starts_with("index\\nested"));
+    }
+}
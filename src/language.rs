@@ -1,5 +1,14 @@
-use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde::{de, de::DeserializeOwned, Deserialize, Deserializer, Serialize};
 use std::collections::HashMap;
+use std::path::Path;
+
+use crate::language_iter::split_path;
+use crate::serialization::{resource_path, FormatVersion, Resource};
+use crate::{
+    LanguageMetadata, LocaleData, OrdinalStrings, PluralRules, ResourceInfo, SelectStrings,
+    StringCategories, StringEntries, StringLeaves, StringNotes, StringPath, TextDirection,
+    TranslationStatus,
+};
 
 /// Part of a path to a string
 #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
@@ -8,33 +17,130 @@ pub enum LanguageStringObject {
     /// A string endpoint
     Direct(String),
 
+    /// A string endpoint with variants chosen by a selector
+    Select(SelectStrings),
+
+    /// A string endpoint with variants chosen by the ordinal form of a position
+    Ordinal(OrdinalStrings),
+
     /// Part of a path to an endpoint
+    #[serde(deserialize_with = "LanguageStringObject::deserialize_category")]
     Category(HashMap<String, LanguageStringObject>),
 }
 
 impl LanguageStringObject {
+    /// Deserialize a category, rejecting variants that failed to parse as variants
+    fn deserialize_category<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<HashMap<String, LanguageStringObject>, D::Error> {
+        let category = HashMap::<String, LanguageStringObject>::deserialize(deserializer)?;
+        match category
+            .keys()
+            .find(|name| *name == "$select" || *name == "$ordinal")
+        {
+            Some(name) => Err(de::Error::custom(format!("invalid {} variants", name))),
+            None => Ok(category),
+        }
+    }
+
     /// Flatten a LanguageStringObject tree into a flat object
     pub fn flatten(&self, own_key: &str) -> HashMap<String, String> {
         let mut map = HashMap::<String, String>::default();
         match self {
-            LanguageStringObject::Direct(s) => {
-                map.insert(own_key.to_string(), s.clone());
-            }
             LanguageStringObject::Category(c) => map.extend(Self::flatten_all(c, Some(own_key))),
+            _ => {
+                map.insert(
+                    own_key.to_string(),
+                    self.text().unwrap_or_default().to_string(),
+                );
+            }
         };
         map
     }
 
+    /// Return the text of a string endpoint, or None for a category
+    /// Strings with variants return their `other` variant
+    pub fn text(&self) -> Option<&str> {
+        match self {
+            LanguageStringObject::Direct(s) => Some(s),
+            LanguageStringObject::Select(s) => Some(s.other().unwrap_or_default()),
+            LanguageStringObject::Ordinal(s) => Some(s.other().unwrap_or_default()),
+            LanguageStringObject::Category(_) => None,
+        }
+    }
+
+    /// Return the text of every variant of a string endpoint, or nothing for a category
+    pub fn texts(&self) -> Vec<&str> {
+        match self {
+            LanguageStringObject::Direct(s) => vec![s],
+            LanguageStringObject::Select(s) => s.variants().values().map(String::as_str).collect(),
+            LanguageStringObject::Ordinal(s) => s.variants().values().map(String::as_str).collect(),
+            LanguageStringObject::Category(_) => vec![],
+        }
+    }
+
+    /// Iterate depth-first over this object and everything below it, without cloning
+    pub fn entries(&self) -> StringEntries<'_> {
+        StringEntries::from_object(StringPath::default(), self)
+    }
+
+    /// Replace every string endpoint below this object
+    pub(crate) fn map_strings<F: FnMut(&str) -> String>(&mut self, f: &mut F) {
+        match self {
+            LanguageStringObject::Direct(s) => *s = f(s),
+            LanguageStringObject::Select(s) => {
+                s.variants_mut().values_mut().for_each(|s| *s = f(s))
+            }
+            LanguageStringObject::Ordinal(s) => {
+                s.variants_mut().values_mut().for_each(|s| *s = f(s))
+            }
+            LanguageStringObject::Category(c) => c.values_mut().for_each(|o| o.map_strings(f)),
+        }
+    }
+
+    fn merge_all(
+        target: &mut HashMap<String, LanguageStringObject>,
+        source: &HashMap<String, LanguageStringObject>,
+        strategy: MergeStrategy,
+        root_key: Option<&str>,
+        conflicts: &mut Vec<String>,
+    ) {
+        for (name, object) in source {
+            let key = if let Some(root_key) = root_key {
+                format!("{}\\{}", root_key, name)
+            } else {
+                name.clone()
+            };
+
+            match (target.get_mut(name), object) {
+                (None, _) => {
+                    target.insert(name.clone(), object.clone());
+                }
+                (
+                    Some(LanguageStringObject::Category(existing)),
+                    LanguageStringObject::Category(incoming),
+                ) => Self::merge_all(existing, incoming, strategy, Some(&key), conflicts),
+                (Some(existing), _) => {
+                    if existing != object {
+                        conflicts.push(key);
+                        if strategy == MergeStrategy::Overwrite {
+                            *existing = object.clone();
+                        }
+                    }
+                }
+            }
+        }
+    }
+
     fn flatten_all(
         c: &HashMap<String, LanguageStringObject>,
         root_key: Option<&str>,
     ) -> HashMap<String, String> {
         let mut map = HashMap::<String, String>::default();
         c.iter().for_each(|e| {
-            let key = if root_key.is_some() {
-                format!("{}\\{}", root_key.unwrap(), e.0)
-            } else {
-                e.0.clone()
+            let key = match root_key {
+                Some(root_key) => format!("{}\\{}", root_key, e.0),
+                None => e.0.clone(),
             };
             map.extend(e.1.flatten(&key))
         });
@@ -42,18 +148,51 @@ impl LanguageStringObject {
     }
 }
 
+/// How conflicting entries are resolved when merging languages
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MergeStrategy {
+    /// Keep the entries already present in the target language
+    KeepExisting,
+
+    /// Replace existing entries with the ones from the merged language
+    Overwrite,
+}
+
 /// Represents a single language lookup instance
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct Language {
+    #[serde(default)]
+    format_version: FormatVersion,
+
     name: String,
     short_name: String,
     strings: HashMap<String, LanguageStringObject>,
 
-    #[serde(default)]
-    resources: HashMap<String, Vec<u8>>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    notes: HashMap<String, StringNotes>,
 
-    #[serde(skip_serializing, default)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    status: HashMap<String, TranslationStatus>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "HashMap::is_empty",
+        with = "crate::serialization::resources"
+    )]
+    resources: HashMap<String, Resource>,
+
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     attachments: HashMap<String, serde_json::Value>,
+
+    #[serde(default, skip_serializing_if = "LanguageMetadata::is_empty")]
+    metadata: LanguageMetadata,
+
+    #[serde(
+        default,
+        skip_serializing_if = "LocaleData::is_empty",
+        deserialize_with = "LocaleData::deserialize_valid"
+    )]
+    locale: LocaleData,
 }
 
 impl Language {
@@ -70,11 +209,19 @@ impl Language {
         resources: HashMap<String, Vec<u8>>,
     ) -> Self {
         Self {
+            format_version: FormatVersion,
             name,
             short_name,
             strings,
-            resources,
+            notes: HashMap::default(),
+            status: HashMap::default(),
+            resources: resources
+                .into_iter()
+                .map(|(name, bytes)| (name, Resource::Embedded(bytes)))
+                .collect(),
             attachments: HashMap::default(),
+            metadata: LanguageMetadata::default(),
+            locale: LocaleData::default(),
         }
     }
 
@@ -101,17 +248,79 @@ impl Language {
         }
     }
 
+    /// Merge the strings, notes, status, resources and attachments of another language into this one
+    /// Returns the paths of every entry that differed between the two languages
+    ///
+    /// # Arguments
+    /// * `other` - Language to merge in
+    /// * `strategy` - How to resolve entries present in both languages
+    pub fn merge(&mut self, other: &Language, strategy: MergeStrategy) -> Vec<String> {
+        let mut conflicts = vec![];
+        LanguageStringObject::merge_all(
+            &mut self.strings,
+            &other.strings,
+            strategy,
+            None,
+            &mut conflicts,
+        );
+
+        for (path, notes) in &other.notes {
+            if strategy == MergeStrategy::Overwrite || !self.notes.contains_key(path) {
+                self.notes.insert(path.clone(), notes.clone());
+            }
+        }
+
+        for (path, status) in &other.status {
+            if strategy == MergeStrategy::Overwrite || !self.status.contains_key(path) {
+                self.status.insert(path.clone(), status.clone());
+            }
+        }
+
+        for (name, resource) in &other.resources {
+            if strategy == MergeStrategy::Overwrite || !self.resources.contains_key(name) {
+                self.resources.insert(name.clone(), resource.clone());
+            }
+        }
+
+        for (name, attachment) in &other.attachments {
+            if strategy == MergeStrategy::Overwrite || !self.attachments.contains_key(name) {
+                self.attachments.insert(name.clone(), attachment.clone());
+            }
+        }
+
+        if strategy == MergeStrategy::Overwrite && !other.metadata.is_empty()
+            || self.metadata.is_empty()
+        {
+            self.metadata = other.metadata.clone();
+        }
+
+        if strategy == MergeStrategy::Overwrite && !other.locale.is_empty()
+            || self.locale.is_empty()
+        {
+            self.locale = other.locale.clone();
+        }
+
+        conflicts
+    }
+
     /// Read language from a JSON string
+    /// Resources given here are added to any resources stored in the JSON, replacing declared ones
+    /// Resources declared by path stay unavailable until [`Language::load_declared_resources`] is called
     ///
     /// # Arguments
-    /// * `path` - Path to the file
+    /// * `json` - JSON source of the language
+    /// * `resources` - Additional resources to embed
     pub fn new_from_string(
         json: &str,
         resources: HashMap<String, Vec<u8>>,
     ) -> Result<Self, String> {
         match serde_json::from_str::<Self>(json) {
             Ok(mut lang) => {
-                lang.resources = resources;
+                lang.resources.extend(
+                    resources
+                        .into_iter()
+                        .map(|(name, bytes)| (name, Resource::Embedded(bytes))),
+                );
                 Ok(lang)
             }
             Err(e) => Err(e.to_string()),
@@ -119,16 +328,73 @@ impl Language {
     }
 
     /// Read language from a file
+    /// Resources declared in the file are loaded, relative to the file's directory
     ///
     /// # Arguments
     /// * `path` - Path to the file
     pub fn new_from_file(path: &str, resources: HashMap<String, Vec<u8>>) -> Result<Self, String> {
         match std::fs::read_to_string(path) {
-            Ok(json) => Self::new_from_string(&json, resources),
+            Ok(json) => {
+                let mut lang = Self::new_from_string(&json, resources)?;
+                let directory = Path::new(path).parent().unwrap_or(Path::new(""));
+                lang.load_declared_resources(directory)?;
+                Ok(lang)
+            }
             Err(e) => Err(e.to_string()),
         }
     }
 
+    /// Load every resource declared by path in the language file, such as `"logo": "./logo.png"`
+    /// Paths must stay inside the directory; absolute paths and `..` are rejected
+    ///
+    /// # Arguments
+    /// * `directory` - Directory the paths are relative to; usually the one containing the language file
+    pub fn load_declared_resources(&mut self, directory: &Path) -> Result<(), String> {
+        for (name, path) in self.owned_declared_resources() {
+            let path = resource_path(directory, &path)?;
+            let bytes = std::fs::read(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+            self.embed_resource(&name, bytes);
+        }
+        Ok(())
+    }
+
+    /// Iterate over the resources declared by path in the language file that are not loaded yet
+    /// Returns the name of each resource, and its path
+    pub fn declared_resources(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.resources
+            .iter()
+            .filter_map(|(name, resource)| match resource {
+                Resource::File(path) => Some((name.as_str(), path.as_str())),
+                Resource::Embedded(_) => None,
+            })
+    }
+
+    /// Collect the resources declared by path, so they can be loaded while the language is modified
+    pub(crate) fn owned_declared_resources(&self) -> Vec<(String, String)> {
+        self.declared_resources()
+            .map(|(name, path)| (name.to_string(), path.to_string()))
+            .collect()
+    }
+
+    /// Embed the contents of a resource, replacing any resource declared with the same name
+    pub(crate) fn embed_resource(&mut self, name: &str, bytes: Vec<u8>) {
+        self.resources
+            .insert(name.to_string(), Resource::Embedded(bytes));
+    }
+
+    /// Replace the name and code of the language
+    pub(crate) fn set_names(&mut self, name: &str, short_name: &str) {
+        self.name = name.to_string();
+        self.short_name = short_name.to_string();
+    }
+
+    /// Replace every string in the language
+    pub(crate) fn map_strings<F: FnMut(&str) -> String>(&mut self, mut f: F) {
+        self.strings
+            .values_mut()
+            .for_each(|o| o.map_strings(&mut f));
+    }
+
     /// Get full language name
     pub fn name(&self) -> &str {
         &self.name
@@ -139,28 +405,133 @@ impl Language {
         &self.short_name
     }
 
+    /// Get the information declared about this language
+    /// See [`Language::direction`] and the other accessors for values with their defaults filled in
+    pub fn metadata(&self) -> &LanguageMetadata {
+        &self.metadata
+    }
+
+    /// Get the information declared about this language, for editing
+    pub fn metadata_mut(&mut self) -> &mut LanguageMetadata {
+        &mut self.metadata
+    }
+
+    /// Get the direction the language is written in
+    pub fn direction(&self) -> TextDirection {
+        self.metadata.direction(&self.short_name)
+    }
+
+    /// Get the ISO 15924 code of the script the language is written in, such as `Arab`
+    pub fn script(&self) -> &str {
+        self.metadata.script(&self.short_name)
+    }
+
+    /// Get the name of the language written in the language itself
+    /// Defaults to the full language name
+    pub fn native_name(&self) -> &str {
+        self.metadata.native_name.as_deref().unwrap_or(&self.name)
+    }
+
+    /// Get the name of the language written in English
+    /// Defaults to the full language name if the language is not well known
+    pub fn english_name(&self) -> &str {
+        self.metadata
+            .english_name(&self.short_name)
+            .unwrap_or(&self.name)
+    }
+
+    /// Get the region the language is used in, such as `EG`
+    pub fn region(&self) -> Option<&str> {
+        self.metadata.region(&self.short_name)
+    }
+
+    /// Get the plural rules of the language
+    pub fn plural_rules(&self) -> PluralRules {
+        PluralRules::for_language(self.metadata.plural_rules(&self.short_name))
+    }
+
+    /// Get the formatting conventions customized by this language
+    pub fn locale(&self) -> &LocaleData {
+        &self.locale
+    }
+
+    /// Get the formatting conventions customized by this language, for editing
+    /// Lists of date names with the wrong length are ignored when formatting
+    pub fn locale_mut(&mut self) -> &mut LocaleData {
+        &mut self.locale
+    }
+
     /// Get language lookup table
+    /// This builds a new flattened copy of every string; see [`Language::leaves`] to iterate without cloning
     pub fn strings(&self) -> HashMap<String, String> {
         LanguageStringObject::flatten_all(&self.strings, None)
     }
 
+    /// Iterate depth-first over every string and category in the language, with their paths
+    pub fn entries(&self) -> StringEntries<'_> {
+        StringEntries::from_map(&self.strings)
+    }
+
+    /// Iterate depth-first over every string in the language, with their paths
+    pub fn leaves(&self) -> StringLeaves<'_> {
+        StringLeaves::new(self.entries())
+    }
+
+    /// Iterate depth-first over every category in the language, with their paths
+    pub fn categories(&self) -> StringCategories<'_> {
+        StringCategories::new(self.entries())
+    }
+
+    /// Find the string or category at a given path
+    ///
+    /// # Arguments
+    /// * `path` - Path to the entry
+    pub fn get_object(&self, path: &str) -> Option<&LanguageStringObject> {
+        self.find_object(path).map(|(_, object)| object)
+    }
+
+    /// List the paths of every string under a category
+    ///
+    /// # Arguments
+    /// * `prefix` - Path to the category; an empty prefix lists every key
+    pub fn keys_under(&self, prefix: &str) -> impl Iterator<Item = StringPath<'_>> {
+        let entries = if split_path(prefix).is_empty() {
+            Some(self.entries())
+        } else {
+            self.find_object(prefix)
+                .map(|(path, object)| StringEntries::from_object(path, object))
+        };
+
+        entries
+            .map(StringLeaves::new)
+            .into_iter()
+            .flatten()
+            .map(|(path, _)| path)
+    }
+
+    /// Find every string whose path matches a glob-style pattern
+    /// `*` and `?` match within a single segment, and `**` matches any number of segments
+    ///
+    /// # Arguments
+    /// * `pattern` - Pattern to match, such as `menu\\*\\tooltip`
+    pub fn query<'a>(
+        &'a self,
+        pattern: &'a str,
+    ) -> impl Iterator<Item = (StringPath<'a>, &'a str)> {
+        self.leaves().filter(move |(path, _)| path.matches(pattern))
+    }
+
     /// Look up a string in the given language
     ///
     /// # Arguments
     /// * `name` - String to find
     pub fn get(&self, name: &str) -> Option<&str> {
-        let mut path = name.split("\\").peekable();
-        if path.peek().is_none() {
-            return None;
-        }
-
-        let mut pos = self.strings.get(path.next().unwrap());
+        let mut path = name.split("\\");
+        let mut pos = self.strings.get(path.next()?);
         for item in path {
-            if pos.is_none() {
-                return None;
-            }
-            match pos.unwrap() {
+            match pos? {
                 LanguageStringObject::Direct(s) => return Some(s),
+                LanguageStringObject::Select(_) | LanguageStringObject::Ordinal(_) => return None,
                 LanguageStringObject::Category(c) => pos = c.get(item),
             }
         }
@@ -168,6 +539,8 @@ impl Language {
         if let Some(pos) = pos {
             match pos {
                 LanguageStringObject::Direct(s) => Some(s),
+                LanguageStringObject::Select(s) => s.other(),
+                LanguageStringObject::Ordinal(s) => s.other(),
                 LanguageStringObject::Category(_) => None,
             }
         } else {
@@ -175,21 +548,335 @@ impl Language {
         }
     }
 
+    /// Set the string at a given path, creating any missing categories along the way
+    /// Fails if the path passes through an existing string, or names an existing category
+    ///
+    /// # Arguments
+    /// * `path` - Path to the string
+    /// * `value` - New value for the string
+    pub fn set(&mut self, path: &str, value: &str) -> Result<(), String> {
+        self.set_object(path, LanguageStringObject::Direct(value.to_string()))
+    }
+
+    /// Set the variants of the string at a given path, creating any missing categories along the way
+    /// Fails if the path passes through an existing string, names an existing category,
+    /// or the variants have no `other` variant
+    ///
+    /// # Arguments
+    /// * `path` - Path to the string
+    /// * `variants` - New variants for the string
+    pub fn set_select(&mut self, path: &str, variants: SelectStrings) -> Result<(), String> {
+        variants.validate()?;
+        self.set_object(path, LanguageStringObject::Select(variants))
+    }
+
+    /// Set the ordinal variants of the string at a given path, creating any missing categories along the way
+    /// Fails if the path passes through an existing string, names an existing category,
+    /// or the variants have no `other` variant
+    ///
+    /// # Arguments
+    /// * `path` - Path to the string
+    /// * `variants` - New variants for the string
+    pub fn set_ordinal(&mut self, path: &str, variants: OrdinalStrings) -> Result<(), String> {
+        variants.validate()?;
+        self.set_object(path, LanguageStringObject::Ordinal(variants))
+    }
+
+    pub(crate) fn set_object(
+        &mut self,
+        path: &str,
+        object: LanguageStringObject,
+    ) -> Result<(), String> {
+        let (parent, name) = Self::split_parent(path)?;
+        let category = self.category_mut(&parent, true)?;
+        if let Some(LanguageStringObject::Category(_)) = category.get(name) {
+            return Err(format!("{} is a category, not a string", path));
+        }
+
+        category.insert(name.to_string(), object);
+        Ok(())
+    }
+
+    /// Look up the variant of a string for a selector in the given language
+    /// Falls back to the `other` variant; strings without variants are returned as-is
+    ///
+    /// # Arguments
+    /// * `name` - String to find
+    /// * `selector` - Variant to choose, such as `female`
+    pub fn get_select(&self, name: &str, selector: &str) -> Option<&str> {
+        match self.get_object(name)? {
+            LanguageStringObject::Direct(s) => Some(s),
+            LanguageStringObject::Select(s) => s.get(selector),
+            LanguageStringObject::Ordinal(s) => s.other(),
+            LanguageStringObject::Category(_) => None,
+        }
+    }
+
+    /// Look up the variant of a string for a position in the given language, as in "3rd place"
+    /// The variant is chosen using the language's ordinal rules, falling back to the `other` variant;
+    /// strings without variants are returned as-is
+    ///
+    /// # Arguments
+    /// * `name` - String to find
+    /// * `n` - Position being written
+    pub fn get_ordinal(&self, name: &str, n: i64) -> Option<&str> {
+        match self.get_object(name)? {
+            LanguageStringObject::Direct(s) => Some(s),
+            LanguageStringObject::Select(s) => s.other(),
+            LanguageStringObject::Ordinal(s) => s.get(self.plural_rules().ordinal(n)),
+            LanguageStringObject::Category(_) => None,
+        }
+    }
+
+    /// Create an empty category at a given path, creating any missing categories along the way
+    /// Does nothing if the category already exists
+    ///
+    /// # Arguments
+    /// * `path` - Path to the category
+    pub fn insert_category(&mut self, path: &str) -> Result<(), String> {
+        let segments = split_path(path);
+        if segments.is_empty() {
+            return Err("Empty path".to_string());
+        }
+
+        self.category_mut(&segments, true).map(|_| ())
+    }
+
+    /// Remove the string or category at a given path
+    /// Returns the removed entry, if there was one
+    ///
+    /// # Arguments
+    /// * `path` - Path to the entry
+    pub fn remove(&mut self, path: &str) -> Option<LanguageStringObject> {
+        let (parent, name) = Self::split_parent(path).ok()?;
+        let object = self.category_mut(&parent, false).ok()?.remove(name)?;
+        take_under(&mut self.notes, path);
+        take_under(&mut self.status, path);
+        Some(object)
+    }
+
+    /// Move the string or category at a given path to a new path
+    /// Fails if nothing exists at the old path, or if something already exists at the new one
+    ///
+    /// # Arguments
+    /// * `old_path` - Current path to the entry
+    /// * `new_path` - New path for the entry
+    pub fn rename(&mut self, old_path: &str, new_path: &str) -> Result<(), String> {
+        let (old_segments, new_segments) = (split_path(old_path), split_path(new_path));
+        if new_segments.starts_with(&old_segments) {
+            return Err(format!("Cannot move {} inside itself", old_path));
+        }
+        if self.get_object(old_path).is_none() {
+            return Err(format!("{} does not exist", old_path));
+        }
+        if self.get_object(new_path).is_some() {
+            return Err(format!("{} already exists", new_path));
+        }
+
+        // Make sure the destination can exist before detaching the entry
+        let (parent, name) = Self::split_parent(new_path)?;
+        self.category_mut(&parent, true)?;
+        let notes = take_under(&mut self.notes, old_path);
+        let status = take_under(&mut self.status, old_path);
+        if let Some(object) = self.remove(old_path) {
+            self.category_mut(&parent, true)?
+                .insert(name.to_string(), object);
+        }
+
+        let new_path = new_segments.join("\\");
+        for (suffix, notes) in notes {
+            self.notes.insert(format!("{}{}", new_path, suffix), notes);
+        }
+        for (suffix, status) in status {
+            self.status
+                .insert(format!("{}{}", new_path, suffix), status);
+        }
+        Ok(())
+    }
+
+    /// Get the translator notes for a string
+    ///
+    /// # Arguments
+    /// * `path` - Path to the string
+    pub fn notes(&self, path: &str) -> Option<&StringNotes> {
+        self.notes.get(&split_path(path).join("\\"))
+    }
+
+    /// Iterate over the translator notes of every string that has them, with their paths
+    pub fn all_notes(&self) -> impl Iterator<Item = (&str, &StringNotes)> {
+        self.notes
+            .iter()
+            .map(|(path, notes)| (path.as_str(), notes))
+    }
+
+    /// Set the translator notes for a string; empty notes are removed
+    /// The string does not need to exist in this language
+    ///
+    /// # Arguments
+    /// * `path` - Path to the string
+    /// * `notes` - New notes for the string
+    pub fn set_notes(&mut self, path: &str, notes: StringNotes) -> Result<(), String> {
+        let segments = split_path(path);
+        if segments.is_empty() {
+            return Err("Empty path".to_string());
+        }
+
+        if notes.is_empty() {
+            self.notes.remove(&segments.join("\\"));
+        } else {
+            self.notes.insert(segments.join("\\"), notes);
+        }
+        Ok(())
+    }
+
+    /// Get the translation status of a string
+    ///
+    /// # Arguments
+    /// * `path` - Path to the string
+    pub fn status(&self, path: &str) -> Option<&TranslationStatus> {
+        self.status.get(&split_path(path).join("\\"))
+    }
+
+    /// Iterate over the translation status of every string that has one, with their paths
+    pub fn all_statuses(&self) -> impl Iterator<Item = (&str, &TranslationStatus)> {
+        self.status
+            .iter()
+            .map(|(path, status)| (path.as_str(), status))
+    }
+
+    /// Set the translation status of a string
+    /// See [`crate::LanguageSet::set_status`] to record the source text from the fallback language
+    ///
+    /// # Arguments
+    /// * `path` - Path to the string
+    /// * `status` - New status for the string, or None to remove it
+    pub fn set_status(
+        &mut self,
+        path: &str,
+        status: Option<TranslationStatus>,
+    ) -> Result<(), String> {
+        let segments = split_path(path);
+        if segments.is_empty() {
+            return Err("Empty path".to_string());
+        }
+
+        match status {
+            Some(status) => self.status.insert(segments.join("\\"), status),
+            None => self.status.remove(&segments.join("\\")),
+        };
+        Ok(())
+    }
+
+    fn split_parent(path: &str) -> Result<(Vec<&str>, &str), String> {
+        let mut segments = split_path(path);
+        match segments.pop() {
+            Some(name) => Ok((segments, name)),
+            None => Err("Empty path".to_string()),
+        }
+    }
+
+    fn category_mut(
+        &mut self,
+        segments: &[&str],
+        create: bool,
+    ) -> Result<&mut HashMap<String, LanguageStringObject>, String> {
+        let mut category = &mut self.strings;
+        for (i, segment) in segments.iter().enumerate() {
+            if create && !category.contains_key(*segment) {
+                category.insert(
+                    segment.to_string(),
+                    LanguageStringObject::Category(HashMap::default()),
+                );
+            }
+
+            category = match category.get_mut(*segment) {
+                Some(LanguageStringObject::Category(c)) => c,
+                Some(
+                    LanguageStringObject::Direct(_)
+                    | LanguageStringObject::Select(_)
+                    | LanguageStringObject::Ordinal(_),
+                ) => {
+                    return Err(format!(
+                        "{} is a string, not a category",
+                        segments[..=i].join("\\")
+                    ))
+                }
+                None => return Err(format!("{} does not exist", segments[..=i].join("\\"))),
+            };
+        }
+        Ok(category)
+    }
+
+    fn find_object(&self, path: &str) -> Option<(StringPath<'_>, &LanguageStringObject)> {
+        let mut path = split_path(path).into_iter();
+        let (key, mut pos) = self.strings.get_key_value(path.next()?)?;
+        let mut segments = vec![key.as_str()];
+        for item in path {
+            match pos {
+                LanguageStringObject::Category(c) => {
+                    let (key, object) = c.get_key_value(item)?;
+                    segments.push(key.as_str());
+                    pos = object;
+                }
+                _ => return None,
+            }
+        }
+        Some((StringPath::from(segments), pos))
+    }
+
     /// Return an embedded resource as a utf8 string
     pub fn utf8_resource(&self, name: &str) -> Option<&str> {
-        self.resources
-            .get(name)
-            .and_then(|bytes| std::str::from_utf8(&bytes.as_slice()).ok())
+        self.binary_resource(name)
+            .and_then(|bytes| std::str::from_utf8(bytes).ok())
     }
 
     /// Return an embedded resource as a slice of bytes
     pub fn binary_resource(&self, name: &str) -> Option<&[u8]> {
         self.resources
             .get(name)
-            .and_then(|bytes| Some(bytes.as_slice()))
+            .and_then(|resource| match resource {
+                Resource::Embedded(bytes) => Some(bytes.as_slice()),
+                Resource::File(_) => None,
+            })
+    }
+
+    /// Describe an embedded resource, with its type, size and hash
+    pub fn resource_info(&self, name: &str) -> Option<ResourceInfo> {
+        self.binary_resource(name)
+            .map(|bytes| ResourceInfo::new(name, &self.short_name, bytes))
+    }
+
+    /// Iterate over the names of every embedded resource
+    pub fn resource_names(&self) -> impl Iterator<Item = &str> {
+        self.resources
+            .iter()
+            .filter(|(_, resource)| matches!(resource, Resource::Embedded(_)))
+            .map(|(name, _)| name.as_str())
     }
 }
 
+/// Remove the entries for a path and everything below it from a map keyed by path
+/// Returns the entries, with the part of their path after the given path
+fn take_under<T>(map: &mut HashMap<String, T>, path: &str) -> Vec<(String, T)> {
+    let path = split_path(path).join("\\");
+    let paths: Vec<String> = map
+        .keys()
+        .filter(|p| {
+            p.strip_prefix(&path)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('\\'))
+        })
+        .cloned()
+        .collect();
+
+    paths
+        .into_iter()
+        .filter_map(|p| {
+            let value = map.remove(&p)?;
+            Some((p[path.len()..].to_string(), value))
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod test_token {
     use super::*;
@@ -208,6 +895,49 @@ mod test_token {
     fn test_new_from_file() {
         let lang = Language::new_from_file("examples/en.lang.json", HashMap::default()).unwrap();
         assert_eq!(lang.short_name(), "en");
+
+        let lang = Language::new_from_file("examples/fr.lang.json", HashMap::default()).unwrap();
+        assert_eq!(
+            lang.utf8_resource("welcome"),
+            Some("Bienvenue dans embedded-lang!\n")
+        );
+        assert_eq!(lang.declared_resources().count(), 0);
+    }
+
+    #[test]
+    fn test_declared_resources() {
+        let json = r#"{"name": "Français", "short_name": "fr", "strings": {},
+            "resources": {"welcome": "./resources/fr/welcome.txt", "logo": "./logo.png"}}"#;
+        let mut lang =
+            Language::new_from_string(json, HashMap::from([("logo".to_string(), vec![1, 2, 3])]))
+                .unwrap();
+        assert_eq!(
+            lang.declared_resources().collect::<Vec<_>>(),
+            vec![("welcome", "./resources/fr/welcome.txt")]
+        );
+        assert_eq!(lang.utf8_resource("welcome"), None);
+        assert_eq!(lang.binary_resource("logo"), Some(&[1, 2, 3][..]));
+
+        assert!(lang.load_declared_resources(Path::new("nope")).is_err());
+
+        let escape = r#"{"name": "Français", "short_name": "fr", "strings": {},
+            "resources": {"secret": "../Cargo.toml"}}"#;
+        let mut escaping = Language::new_from_string(escape, HashMap::default()).unwrap();
+        assert!(escaping
+            .load_declared_resources(Path::new("examples"))
+            .unwrap_err()
+            .contains("inside the language's directory"));
+        let absolute = r#"{"name": "Français", "short_name": "fr", "strings": {},
+            "resources": {"secret": "/etc/hostname"}}"#;
+        let mut absolute = Language::new_from_string(absolute, HashMap::default()).unwrap();
+        assert!(absolute
+            .load_declared_resources(Path::new("examples"))
+            .is_err());
+        assert!(lang.load_declared_resources(Path::new("examples")).is_ok());
+        assert!(lang.utf8_resource("welcome").is_some());
+        assert!(serde_json::to_string(&lang)
+            .unwrap()
+            .contains("data:application/octet-stream;base64,"));
     }
 
     #[test]
@@ -229,4 +959,288 @@ mod test_token {
         assert_eq!(lang.get("hello_msg"), Some("hello world!"));
         assert_eq!(lang.get("goodbye_msg"), None);
     }
+
+    #[test]
+    fn test_leaves() {
+        let lang = embedded_language!("../examples/en.lang.json");
+
+        let mut leaves: Vec<(String, &str)> = lang
+            .leaves()
+            .map(|(path, s)| (path.to_string(), s))
+            .collect();
+        leaves.sort();
+        let mut strings: Vec<(String, String)> = lang.strings().into_iter().collect();
+        strings.sort();
+
+        assert_eq!(leaves.len(), strings.len());
+        for (leaf, string) in leaves.iter().zip(strings.iter()) {
+            assert_eq!(leaf.0, string.0);
+            assert_eq!(leaf.1, string.1);
+        }
+    }
+
+    #[test]
+    fn test_entries() {
+        let lang = embedded_language!("../examples/en.lang.json");
+
+        let entries: Vec<String> = lang.entries().map(|(path, _)| path.to_string()).collect();
+        assert_eq!(entries.len(), 6);
+
+        let category = entries.iter().position(|p| p == "category\\category2");
+        let leaf = entries.iter().position(|p| p == "category\\category2\\foo");
+        assert!(category < leaf);
+
+        let mut categories: Vec<String> = lang
+            .categories()
+            .map(|(path, _)| path.to_string())
+            .collect();
+        categories.sort();
+        assert_eq!(categories, vec!["category", "category\\category2"]);
+    }
+
+    #[test]
+    fn test_keys_under() {
+        let lang = embedded_language!("../examples/en.lang.json");
+
+        let keys: Vec<StringPath> = lang.keys_under("category").collect();
+        assert_eq!(keys, vec!["category\\category2\\foo"]);
+
+        assert_eq!(lang.keys_under("").count(), 4);
+        assert_eq!(lang.keys_under("tree").count(), 1);
+        assert_eq!(lang.keys_under("nope").count(), 0);
+    }
+
+    #[test]
+    fn test_query() {
+        let lang = Language::new_from_string(
+            r#"{"name": "English", "short_name": "en", "strings": {
+                "menu": {
+                    "file": {"label": "File", "tooltip": "File menu"},
+                    "edit": {"label": "Edit", "tooltip": "Edit menu"},
+                    "help": "Help"
+                }
+            }}"#,
+            HashMap::default(),
+        )
+        .unwrap();
+
+        let mut tooltips: Vec<&str> = lang.query("menu\\*\\tooltip").map(|(_, s)| s).collect();
+        tooltips.sort();
+        assert_eq!(tooltips, vec!["Edit menu", "File menu"]);
+
+        assert_eq!(lang.query("menu\\**").count(), 5);
+        assert_eq!(lang.query("**\\label").count(), 2);
+        assert_eq!(lang.query("menu\\h*").count(), 1);
+    }
+
+    #[test]
+    fn test_set() {
+        let mut lang = embedded_language!("../examples/en.lang.json");
+
+        lang.set("tree", "oak").unwrap();
+        lang.set("menu\\file\\label", "File").unwrap();
+        lang.set("category\\category2\\baz", "qux").unwrap();
+        assert_eq!(lang.get("tree"), Some("oak"));
+        assert_eq!(lang.get("menu\\file\\label"), Some("File"));
+        assert_eq!(lang.get("category\\category2\\foo"), Some("bar"));
+        assert_eq!(lang.get("category\\category2\\baz"), Some("qux"));
+
+        assert!(lang.set("tree\\leaf", "leaf").is_err());
+        assert!(lang.set("category", "leaf").is_err());
+        assert!(lang.set("", "leaf").is_err());
+    }
+
+    #[test]
+    fn test_select() {
+        let mut lang = Language::new_from_string(
+            r#"{"name": "English", "short_name": "en", "strings": {
+                "menu": {"greeting": {"$select": {"male": "He", "other": "They"}}}
+            }}"#,
+            HashMap::default(),
+        )
+        .unwrap();
+
+        assert_eq!(lang.get("menu\\greeting"), Some("They"));
+        assert_eq!(lang.get_select("menu\\greeting", "male"), Some("He"));
+        assert_eq!(lang.get_select("menu\\greeting", "female"), Some("They"));
+        assert_eq!(lang.categories().count(), 1);
+        assert!(lang.set("menu\\greeting\\x", "x").is_err());
+
+        lang.set_select(
+            "tree",
+            SelectStrings::new(HashMap::from([("other".to_string(), "tree".to_string())])).unwrap(),
+        )
+        .unwrap();
+        let mut variants =
+            SelectStrings::new(HashMap::from([("other".to_string(), "x".to_string())])).unwrap();
+        variants.variants_mut().clear();
+        assert!(lang.set_select("bush", variants).is_err());
+        assert!(Language::new_from_string(
+            r#"{"name": "English", "short_name": "en", "strings": {
+                "menu": {"greeting": {"$select": {"male": "He"}}}
+            }}"#,
+            HashMap::default(),
+        )
+        .is_err());
+        lang.map_strings(|s| s.to_uppercase());
+        assert_eq!(lang.get_select("tree", "male"), Some("TREE"));
+        assert_eq!(lang.get_select("menu\\greeting", "male"), Some("HE"));
+    }
+
+    #[test]
+    fn test_metadata() {
+        let lang = embedded_language!("../examples/en.lang.json");
+        assert_eq!(lang.direction(), TextDirection::Ltr);
+        assert_eq!(lang.script(), "Latn");
+        assert_eq!(lang.native_name(), "English");
+        assert_eq!(lang.english_name(), "English");
+        assert_eq!(lang.region(), None);
+        assert_eq!(lang.plural_rules().language(), "en");
+
+        let mut lang = Language::new_from_string(
+            r#"{"name": "Kurdî", "short_name": "ku-IQ", "metadata": {
+                "direction": "rtl", "script": "Arab", "english_name": "Kurdish", "plural_rules": "en"
+            }, "strings": {"place": {"$ordinal": {"one": "first", "other": "other"}}}}"#,
+            HashMap::default(),
+        )
+        .unwrap();
+        assert_eq!(lang.direction(), TextDirection::Rtl);
+        assert_eq!(lang.script(), "Arab");
+        assert_eq!(lang.english_name(), "Kurdish");
+        assert_eq!(lang.region(), Some("IQ"));
+        assert_eq!(lang.get_ordinal("place", 21), Some("first"));
+
+        lang.metadata_mut().native_name = Some("کوردی".to_string());
+        assert_eq!(lang.native_name(), "کوردی");
+    }
+
+    #[test]
+    fn test_notes() {
+        let mut lang = Language::new_from_string(
+            r#"{"name": "English", "short_name": "en",
+                "strings": {"menu": {"save": "Save", "open": "Open"}},
+                "notes": {"menu\\save": {"context": "button", "max_length": 10}}
+            }"#,
+            HashMap::default(),
+        )
+        .unwrap();
+
+        assert_eq!(lang.notes("menu\\save").unwrap().max_length, Some(10));
+        assert_eq!(
+            lang.notes("\\menu\\save\\").unwrap().context.as_deref(),
+            Some("button")
+        );
+        assert!(lang.notes("menu\\open").is_none());
+
+        lang.set_notes(
+            "menu\\open",
+            StringNotes {
+                description: Some("Opens a file".to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(lang.all_notes().count(), 2);
+        assert!(lang.set_notes("", StringNotes::default()).is_err());
+
+        lang.rename("menu", "file").unwrap();
+        assert!(lang.notes("menu\\save").is_none());
+        assert_eq!(lang.notes("file\\save").unwrap().max_length, Some(10));
+
+        lang.set_status(
+            "file\\open",
+            Some(TranslationStatus {
+                state: crate::TranslationState::Approved,
+                source_hash: None,
+            }),
+        )
+        .unwrap();
+        assert_eq!(lang.all_statuses().count(), 1);
+        lang.remove("file\\open");
+        assert!(lang.notes("file\\open").is_none());
+        assert!(lang.status("file\\open").is_none());
+        lang.set_notes("file\\save", StringNotes::default())
+            .unwrap();
+        assert_eq!(lang.all_notes().count(), 0);
+    }
+
+    #[test]
+    fn test_insert_category() {
+        let mut lang = embedded_language!("../examples/en.lang.json");
+
+        lang.insert_category("a\\b").unwrap();
+        lang.insert_category("category").unwrap();
+        assert_eq!(
+            lang.get_object("a\\b"),
+            Some(&LanguageStringObject::Category(HashMap::default()))
+        );
+        assert_eq!(lang.get("category\\category2\\foo"), Some("bar"));
+
+        assert!(lang.insert_category("tree").is_err());
+        assert!(lang.insert_category("tree\\leaf").is_err());
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut lang = embedded_language!("../examples/en.lang.json");
+
+        assert_eq!(
+            lang.remove("tree"),
+            Some(LanguageStringObject::Direct("tree".to_string()))
+        );
+        assert!(lang.remove("category\\category2").is_some());
+        assert_eq!(lang.get("tree"), None);
+        assert_eq!(lang.get("category\\category2\\foo"), None);
+        assert!(lang.get_object("category").is_some());
+
+        assert_eq!(lang.remove("tree"), None);
+        assert_eq!(lang.remove("mustard\\seed"), None);
+    }
+
+    #[test]
+    fn test_rename() {
+        let mut lang = embedded_language!("../examples/en.lang.json");
+
+        lang.rename("tree", "plants\\tree").unwrap();
+        lang.rename("category\\category2", "other").unwrap();
+        assert_eq!(lang.get("tree"), None);
+        assert_eq!(lang.get("plants\\tree"), Some("tree"));
+        assert_eq!(lang.get("other\\foo"), Some("bar"));
+
+        assert!(lang.rename("nope", "nope2").is_err());
+        assert!(lang.rename("mustard", "hello_msg").is_err());
+        assert!(lang.rename("mustard", "hello_msg\\mustard").is_err());
+        assert!(lang.rename("plants", "plants\\inner").is_err());
+        assert_eq!(lang.get("mustard"), Some("mustard"));
+    }
+
+    #[test]
+    fn test_merge() {
+        let overrides = Language::new_from_string(
+            r#"{"name": "English", "short_name": "en", "strings": {
+                "tree": "oak",
+                "mustard": "mustard",
+                "category": {"category2": {"baz": "qux"}}
+            }}"#,
+            HashMap::from([("license_file".to_string(), vec![1, 2, 3])]),
+        )
+        .unwrap();
+
+        let mut lang = embedded_language!("../examples/en.lang.json");
+        let conflicts = lang.merge(&overrides, MergeStrategy::KeepExisting);
+        assert_eq!(conflicts, vec!["tree"]);
+        assert_eq!(lang.get("tree"), Some("tree"));
+        assert_eq!(lang.get("category\\category2\\foo"), Some("bar"));
+        assert_eq!(lang.get("category\\category2\\baz"), Some("qux"));
+        assert_eq!(lang.binary_resource("license_file"), Some(&[1, 2, 3][..]));
+        assert_eq!(
+            lang.resource_names().collect::<Vec<_>>(),
+            vec!["license_file"]
+        );
+
+        let mut lang = embedded_language!("../examples/en.lang.json");
+        let conflicts = lang.merge(&overrides, MergeStrategy::Overwrite);
+        assert_eq!(conflicts, vec!["tree"]);
+        assert_eq!(lang.get("tree"), Some("oak"));
+    }
 }
@@ -0,0 +1,210 @@
+use serde::Serialize;
+use serde_json::{ser::PrettyFormatter, Map, Value};
+
+use crate::{Language, LanguageStringObject};
+
+/// File formats languages can be read from and written to
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LanguageFormat {
+    /// The nested JSON format used by `.lang.json` files
+    Json,
+
+    /// JSON with the path of every string flattened into a single object, as `.flat.json` files
+    /// Strings with variants keep their nested form, such as `{"$select": {...}}` or `{"$ordinal": {...}}`
+    FlatJson,
+}
+
+impl LanguageFormat {
+    /// Find a format by name; either `json` or `flat`
+    ///
+    /// # Arguments
+    /// * `name` - Name of the format
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "json" => Some(Self::Json),
+            "flat" => Some(Self::FlatJson),
+            _ => None,
+        }
+    }
+
+    /// Guess the format of a file from its name
+    ///
+    /// # Arguments
+    /// * `path` - Path to the file
+    pub fn from_path(path: &str) -> Self {
+        if path.ends_with(".flat.json") {
+            Self::FlatJson
+        } else {
+            Self::Json
+        }
+    }
+
+    /// Read a language in this format
+    ///
+    /// # Arguments
+    /// * `source` - Contents of the language file
+    pub fn read(&self, source: &str) -> Result<Language, String> {
+        match self {
+            Self::Json => Language::new_from_string(source, Default::default()),
+            Self::FlatJson => {
+                let mut value: Value = serde_json::from_str(source).map_err(|e| e.to_string())?;
+                let strings = match value.get_mut("strings").map(Value::take) {
+                    Some(Value::Object(strings)) => strings,
+                    _ => return Err("missing field `strings`".to_string()),
+                };
+
+                value["strings"] = Value::Object(Map::default());
+                let mut language: Language =
+                    serde_json::from_value(value).map_err(|e| e.to_string())?;
+                for (path, text) in strings {
+                    match serde_json::from_value(text) {
+                        Ok(LanguageStringObject::Category(_)) | Err(_) => {
+                            return Err(format!("{} is not a string", path))
+                        }
+                        Ok(object) => language.set_object(&path, object)?,
+                    }
+                }
+                Ok(language)
+            }
+        }
+    }
+
+    /// Write a language in this format, with sorted keys and 4-space indentation
+    ///
+    /// # Arguments
+    /// * `language` - Language to write
+    pub fn write(&self, language: &Language) -> Result<String, String> {
+        pretty_json(self.value_of(language)?)
+    }
+
+    /// Write a language read from a source in this format, keeping the fields the source left out
+    /// The `format_version` field is only written if the source had one
+    ///
+    /// # Arguments
+    /// * `language` - Language to write
+    /// * `source` - Text the language was read from
+    pub fn rewrite(&self, language: &Language, source: &str) -> Result<String, String> {
+        let mut value = self.value_of(language)?;
+        let original: Value = serde_json::from_str(source).map_err(|e| e.to_string())?;
+        if original.get("format_version").is_none() {
+            if let Some(fields) = value.as_object_mut() {
+                fields.remove("format_version");
+            }
+        }
+        pretty_json(value)
+    }
+
+    fn value_of(&self, language: &Language) -> Result<Value, String> {
+        let mut value = serde_json::to_value(language).map_err(|e| e.to_string())?;
+        if *self == Self::FlatJson {
+            value["strings"] = Value::Object(
+                language
+                    .entries()
+                    .filter_map(|(path, object)| match object {
+                        LanguageStringObject::Category(_) => None,
+                        _ => serde_json::to_value(object)
+                            .ok()
+                            .map(|value| (path.to_string(), value)),
+                    })
+                    .collect(),
+            );
+        }
+        Ok(value)
+    }
+}
+
+/// Write JSON indented by four spaces, as language files are
+fn pretty_json(value: Value) -> Result<String, String> {
+    let mut buffer = vec![];
+    let mut serializer =
+        serde_json::Serializer::with_formatter(&mut buffer, PrettyFormatter::with_indent(b"    "));
+    value
+        .serialize(&mut serializer)
+        .map_err(|e| e.to_string())?;
+    String::from_utf8(buffer).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod test_token {
+    use super::*;
+    use crate as embedded_lang;
+    use crate::embedded_language;
+
+    #[test]
+    fn test_from_path() {
+        assert_eq!(
+            LanguageFormat::from_path("en.flat.json"),
+            LanguageFormat::FlatJson
+        );
+        assert_eq!(
+            LanguageFormat::from_path("en.lang.json"),
+            LanguageFormat::Json
+        );
+        assert_eq!(
+            LanguageFormat::from_name("flat"),
+            Some(LanguageFormat::FlatJson)
+        );
+        assert_eq!(LanguageFormat::from_name("xml"), None);
+    }
+
+    #[test]
+    fn test_json() {
+        let lang = embedded_language!("../examples/en.lang.json");
+        let json = LanguageFormat::Json.write(&lang).unwrap();
+        assert!(json.contains("\n    \"name\": \"English\""));
+        assert_eq!(LanguageFormat::Json.read(&json).unwrap(), lang);
+    }
+
+    #[test]
+    fn test_rewrite() {
+        let source = include_str!("../examples/en.lang.json");
+        let lang = LanguageFormat::Json.read(source).unwrap();
+        let json = LanguageFormat::Json.rewrite(&lang, source).unwrap();
+        assert!(!json.contains("format_version"));
+        assert_eq!(LanguageFormat::Json.read(&json).unwrap(), lang);
+
+        let json = LanguageFormat::Json.write(&lang).unwrap();
+        assert!(json.contains("format_version"));
+        let json = LanguageFormat::Json.rewrite(&lang, &json).unwrap();
+        assert!(json.contains("format_version"));
+    }
+
+    #[test]
+    fn test_flat_json() {
+        let lang = embedded_language!("../examples/en.lang.json");
+        let json = LanguageFormat::FlatJson.write(&lang).unwrap();
+        assert!(json.contains("\"category\\\\category2\\\\foo\": \"bar\""));
+        assert_eq!(LanguageFormat::FlatJson.read(&json).unwrap(), lang);
+
+        assert!(LanguageFormat::FlatJson
+            .read(r#"{"name": "English", "short_name": "en", "strings": {"a": "b", "a\\c": "d"}}"#)
+            .is_err());
+
+        let lang = LanguageFormat::FlatJson
+            .read(r#"{"name": "English", "short_name": "en", "strings": {"a\\b": {"$select": {"male": "He", "other": "They"}}}}"#)
+            .unwrap();
+        assert_eq!(lang.get_select("a\\b", "male"), Some("He"));
+        let json = LanguageFormat::FlatJson.write(&lang).unwrap();
+        assert!(json.contains("\"$select\""));
+        assert_eq!(LanguageFormat::FlatJson.read(&json).unwrap(), lang);
+        assert!(LanguageFormat::FlatJson
+            .read(r#"{"name": "English", "short_name": "en", "strings": {"a": {"b": "c"}}}"#)
+            .is_err());
+
+        let mut lang = LanguageFormat::FlatJson
+            .read(r#"{"name": "English", "short_name": "en", "strings": {"place": {"$ordinal": {"two": "{n}nd", "other": "{n}th"}}}}"#)
+            .unwrap();
+        assert_eq!(lang.get_ordinal("place", 22), Some("{n}nd"));
+        lang.set_notes(
+            "place",
+            crate::StringNotes {
+                max_length: Some(12),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let json = LanguageFormat::FlatJson.write(&lang).unwrap();
+        assert!(json.contains("\"max_length\": 12"));
+        assert_eq!(LanguageFormat::FlatJson.read(&json).unwrap(), lang);
+    }
+}
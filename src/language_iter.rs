@@ -0,0 +1,229 @@
+use std::collections::hash_map;
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::LanguageStringObject;
+
+/// Path to an entry in a language string tree
+/// Segments borrow from the tree, and are joined with `\` when displayed
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct StringPath<'a>(Vec<&'a str>);
+
+impl<'a> StringPath<'a> {
+    /// Return the segments making up this path
+    pub fn segments(&self) -> &[&'a str] {
+        &self.0
+    }
+
+    /// Return the last segment of the path
+    pub fn name(&self) -> Option<&'a str> {
+        self.0.last().copied()
+    }
+
+    /// Return the number of segments in the path
+    pub fn depth(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Check whether this path matches a glob-style pattern
+    /// `*` and `?` match within a single segment, and `**` matches any number of segments
+    ///
+    /// # Arguments
+    /// * `pattern` - Pattern to match, such as `menu\\*\\tooltip`
+    pub fn matches(&self, pattern: &str) -> bool {
+        let pattern = split_path(pattern);
+        match_segments(&pattern, &self.0)
+    }
+
+    fn with(&self, segment: &'a str) -> Self {
+        let mut path = self.0.clone();
+        path.push(segment);
+        Self(path)
+    }
+}
+
+impl<'a> From<Vec<&'a str>> for StringPath<'a> {
+    fn from(segments: Vec<&'a str>) -> Self {
+        Self(segments)
+    }
+}
+
+impl fmt::Display for StringPath<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.join("\\"))
+    }
+}
+
+impl PartialEq<&str> for StringPath<'_> {
+    fn eq(&self, other: &&str) -> bool {
+        self.0.iter().copied().eq(split_path(other))
+    }
+}
+
+/// Depth-first iterator over every entry in a language string tree
+/// Categories are returned before their contents
+pub struct StringEntries<'a> {
+    pending: Option<(StringPath<'a>, &'a LanguageStringObject)>,
+    stack: Vec<hash_map::Iter<'a, String, LanguageStringObject>>,
+    path: StringPath<'a>,
+}
+
+impl<'a> StringEntries<'a> {
+    pub(crate) fn from_map(map: &'a HashMap<String, LanguageStringObject>) -> Self {
+        Self {
+            pending: None,
+            stack: vec![map.iter()],
+            path: StringPath::default(),
+        }
+    }
+
+    pub(crate) fn from_object(path: StringPath<'a>, object: &'a LanguageStringObject) -> Self {
+        Self {
+            pending: Some((path, object)),
+            stack: vec![],
+            path: StringPath::default(),
+        }
+    }
+
+    fn visit(
+        &mut self,
+        path: StringPath<'a>,
+        object: &'a LanguageStringObject,
+    ) -> (StringPath<'a>, &'a LanguageStringObject) {
+        if let LanguageStringObject::Category(c) = object {
+            self.stack.push(c.iter());
+            self.path = path.clone();
+        }
+        (path, object)
+    }
+}
+
+impl<'a> Iterator for StringEntries<'a> {
+    type Item = (StringPath<'a>, &'a LanguageStringObject);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some((path, object)) = self.pending.take() {
+            return Some(self.visit(path, object));
+        }
+
+        loop {
+            match self.stack.last_mut()?.next() {
+                Some((key, object)) => {
+                    let path = self.path.with(key);
+                    return Some(self.visit(path, object));
+                }
+                None => {
+                    self.stack.pop();
+                    self.path.0.pop();
+                }
+            }
+        }
+    }
+}
+
+/// Depth-first iterator over the string endpoints of a language string tree
+/// Strings with variants are returned as their `other` variant
+pub struct StringLeaves<'a>(StringEntries<'a>);
+
+impl<'a> StringLeaves<'a> {
+    pub(crate) fn new(entries: StringEntries<'a>) -> Self {
+        Self(entries)
+    }
+}
+
+impl<'a> Iterator for StringLeaves<'a> {
+    type Item = (StringPath<'a>, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0
+            .find_map(|(path, object)| object.text().map(|s| (path, s)))
+    }
+}
+
+/// Depth-first iterator over the categories of a language string tree
+pub struct StringCategories<'a>(StringEntries<'a>);
+
+impl<'a> StringCategories<'a> {
+    pub(crate) fn new(entries: StringEntries<'a>) -> Self {
+        Self(entries)
+    }
+}
+
+impl<'a> Iterator for StringCategories<'a> {
+    type Item = (StringPath<'a>, &'a HashMap<String, LanguageStringObject>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.find_map(|(path, object)| match object {
+            LanguageStringObject::Category(c) => Some((path, c)),
+            _ => None,
+        })
+    }
+}
+
+pub(crate) fn split_path(path: &str) -> Vec<&str> {
+    path.split('\\').filter(|s| !s.is_empty()).collect()
+}
+
+fn match_segments(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|i| match_segments(rest, &path[i..])),
+        Some((segment, rest)) => match path.split_first() {
+            Some((name, path)) => match_glob(segment, name) && match_segments(rest, path),
+            None => false,
+        },
+    }
+}
+
+fn match_glob(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+
+    // Classic two-pointer wildcard match, backtracking to the last `*`
+    let (mut p, mut n) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, n));
+            p += 1;
+        } else if let Some((sp, sn)) = star {
+            p = sp + 1;
+            n = sn + 1;
+            star = Some((sp, sn + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod test_token {
+    use super::*;
+
+    #[test]
+    fn test_match_glob() {
+        assert!(match_glob("*", "tooltip"));
+        assert!(match_glob("tool*", "tooltip"));
+        assert!(match_glob("t?ol*p", "tooltip"));
+        assert!(!match_glob("tool", "tooltip"));
+        assert!(!match_glob("*x", "tooltip"));
+    }
+
+    #[test]
+    fn test_matches() {
+        let path = StringPath(vec!["menu", "file", "tooltip"]);
+        assert!(path.matches("menu\\*\\tooltip"));
+        assert!(path.matches("menu\\**"));
+        assert!(path.matches("**\\tooltip"));
+        assert!(path.matches("menu\\**\\file\\tooltip"));
+        assert!(!path.matches("menu\\*"));
+        assert!(!path.matches("*\\tooltip"));
+        assert_eq!(path, "menu\\file\\tooltip");
+        assert_eq!(path.to_string(), "menu\\file\\tooltip");
+    }
+}
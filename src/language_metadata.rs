@@ -0,0 +1,297 @@
+use serde::{Deserialize, Serialize};
+
+/// Starts a run of text isolated from its surroundings, with its direction detected from its contents
+pub const FIRST_STRONG_ISOLATE: char = '\u{2068}';
+
+/// Ends a run of text started by [`FIRST_STRONG_ISOLATE`]
+pub const POP_DIRECTIONAL_ISOLATE: char = '\u{2069}';
+
+/// Direction text is written in
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum TextDirection {
+    /// Left to right, as in English
+    #[default]
+    Ltr,
+
+    /// Right to left, as in Arabic or Hebrew
+    Rtl,
+}
+
+impl TextDirection {
+    /// Return the direction a script is written in
+    ///
+    /// # Arguments
+    /// * `script` - ISO 15924 script code, such as `Arab`
+    pub fn for_script(script: &str) -> Self {
+        match script {
+            "Arab" | "Hebr" | "Thaa" | "Syrc" | "Nkoo" | "Adlm" | "Rohg" | "Mand" | "Samr" => {
+                Self::Rtl
+            }
+            _ => Self::Ltr,
+        }
+    }
+}
+
+/// Script used by each language by default, by primary language subtag
+/// Languages not listed are written in the Latin script
+const DEFAULT_SCRIPTS: &[(&str, &str)] = &[
+    ("am", "Ethi"),
+    ("ar", "Arab"),
+    ("be", "Cyrl"),
+    ("bg", "Cyrl"),
+    ("bn", "Beng"),
+    ("dv", "Thaa"),
+    ("el", "Grek"),
+    ("fa", "Arab"),
+    ("gu", "Gujr"),
+    ("he", "Hebr"),
+    ("hi", "Deva"),
+    ("hy", "Armn"),
+    ("iw", "Hebr"),
+    ("ja", "Jpan"),
+    ("ka", "Geor"),
+    ("kk", "Cyrl"),
+    ("km", "Khmr"),
+    ("kn", "Knda"),
+    ("ko", "Kore"),
+    ("ky", "Cyrl"),
+    ("lo", "Laoo"),
+    ("mk", "Cyrl"),
+    ("ml", "Mlym"),
+    ("mn", "Cyrl"),
+    ("mr", "Deva"),
+    ("my", "Mymr"),
+    ("ne", "Deva"),
+    ("pa", "Guru"),
+    ("ps", "Arab"),
+    ("ru", "Cyrl"),
+    ("sd", "Arab"),
+    ("si", "Sinh"),
+    ("sr", "Cyrl"),
+    ("ta", "Taml"),
+    ("te", "Telu"),
+    ("tg", "Cyrl"),
+    ("th", "Thai"),
+    ("ug", "Arab"),
+    ("uk", "Cyrl"),
+    ("ur", "Arab"),
+    ("yi", "Hebr"),
+    ("zh", "Hans"),
+];
+
+/// Regions using traditional Chinese characters by default
+const TRADITIONAL_CHINESE_REGIONS: &[&str] = &["TW", "HK", "MO"];
+
+/// English names of common languages, by primary language subtag
+const ENGLISH_NAMES: &[(&str, &str)] = &[
+    ("ar", "Arabic"),
+    ("cs", "Czech"),
+    ("da", "Danish"),
+    ("de", "German"),
+    ("el", "Greek"),
+    ("en", "English"),
+    ("es", "Spanish"),
+    ("fa", "Persian"),
+    ("fi", "Finnish"),
+    ("fr", "French"),
+    ("he", "Hebrew"),
+    ("hi", "Hindi"),
+    ("hu", "Hungarian"),
+    ("id", "Indonesian"),
+    ("it", "Italian"),
+    ("ja", "Japanese"),
+    ("ko", "Korean"),
+    ("nb", "Norwegian Bokmål"),
+    ("nl", "Dutch"),
+    ("pl", "Polish"),
+    ("pt", "Portuguese"),
+    ("ro", "Romanian"),
+    ("ru", "Russian"),
+    ("sv", "Swedish"),
+    ("th", "Thai"),
+    ("tr", "Turkish"),
+    ("uk", "Ukrainian"),
+    ("ur", "Urdu"),
+    ("vi", "Vietnamese"),
+    ("zh", "Chinese"),
+];
+
+/// Information about a language declared in its `metadata` section
+/// Anything left out is derived from the language's code, such as `ar-EG` or `sr-Latn`
+#[derive(Serialize, Deserialize, Clone, Debug, Default, Eq, PartialEq)]
+pub struct LanguageMetadata {
+    /// Direction the language is written in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub direction: Option<TextDirection>,
+
+    /// ISO 15924 code of the script the language is written in, such as `Latn`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub script: Option<String>,
+
+    /// Name of the language, written in the language itself
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub native_name: Option<String>,
+
+    /// Name of the language, written in English
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub english_name: Option<String>,
+
+    /// Code of the language whose plural rules apply, such as `pt-PT`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plural_rules: Option<String>,
+
+    /// Region the language is used in, such as `EG`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+}
+
+impl LanguageMetadata {
+    /// Return true if nothing is declared
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Return the direction the language is written in
+    /// Defaults to the direction of its script
+    ///
+    /// # Arguments
+    /// * `tag` - Code of the language
+    pub fn direction(&self, tag: &str) -> TextDirection {
+        self.direction
+            .unwrap_or_else(|| TextDirection::for_script(self.script(tag)))
+    }
+
+    /// Return the script the language is written in
+    /// Defaults to the script subtag of the code, then the usual script of the language
+    ///
+    /// # Arguments
+    /// * `tag` - Code of the language
+    pub fn script<'a>(&'a self, tag: &'a str) -> &'a str {
+        if let Some(script) = &self.script {
+            return script;
+        }
+
+        let mut subtags = subtags(tag);
+        let primary = subtags.next().unwrap_or_default();
+        let rest: Vec<&str> = subtags.collect();
+        if let Some(script) = rest.iter().find(|s| is_script(s)) {
+            return script;
+        }
+
+        match find_subtag(DEFAULT_SCRIPTS, primary) {
+            Some("Hans")
+                if self
+                    .region(tag)
+                    .is_some_and(|r| TRADITIONAL_CHINESE_REGIONS.contains(&r)) =>
+            {
+                "Hant"
+            }
+            Some(script) => script,
+            None => "Latn",
+        }
+    }
+
+    /// Return the region the language is used in
+    /// Defaults to the region subtag of the code, if it has one
+    ///
+    /// # Arguments
+    /// * `tag` - Code of the language
+    pub fn region<'a>(&'a self, tag: &'a str) -> Option<&'a str> {
+        match &self.region {
+            Some(region) => Some(region),
+            None => subtags(tag).skip(1).find(|s| is_region(s)),
+        }
+    }
+
+    /// Return the name of the language in English, if it is declared or well known
+    ///
+    /// # Arguments
+    /// * `tag` - Code of the language
+    pub fn english_name(&self, tag: &str) -> Option<&str> {
+        match &self.english_name {
+            Some(name) => Some(name),
+            None => find_subtag(ENGLISH_NAMES, subtags(tag).next().unwrap_or_default()),
+        }
+    }
+
+    /// Return the code of the language whose plural rules apply
+    /// Defaults to the code of the language itself
+    ///
+    /// # Arguments
+    /// * `tag` - Code of the language
+    pub fn plural_rules<'a>(&'a self, tag: &'a str) -> &'a str {
+        self.plural_rules.as_deref().unwrap_or(tag)
+    }
+}
+
+/// Wrap text so its direction does not affect the text around it
+/// Keeps left-to-right values, such as names or numbers, readable inside right-to-left strings
+///
+/// # Arguments
+/// * `text` - Text to wrap
+pub fn bidi_isolate(text: &str) -> String {
+    format!(
+        "{}{}{}",
+        FIRST_STRONG_ISOLATE, text, POP_DIRECTIONAL_ISOLATE
+    )
+}
+
+fn subtags(tag: &str) -> impl Iterator<Item = &str> {
+    tag.split(['-', '_']).filter(|s| !s.is_empty())
+}
+
+fn find_subtag(table: &[(&str, &'static str)], primary: &str) -> Option<&'static str> {
+    table
+        .iter()
+        .find(|(code, _)| code.eq_ignore_ascii_case(primary))
+        .map(|(_, value)| *value)
+}
+
+fn is_script(subtag: &str) -> bool {
+    subtag.len() == 4 && subtag.chars().all(|c| c.is_ascii_alphabetic())
+}
+
+fn is_region(subtag: &str) -> bool {
+    (subtag.len() == 2 && subtag.chars().all(|c| c.is_ascii_alphabetic()))
+        || (subtag.len() == 3 && subtag.chars().all(|c| c.is_ascii_digit()))
+}
+
+#[cfg(test)]
+mod test_token {
+    use super::*;
+
+    #[test]
+    fn test_defaults() {
+        let metadata = LanguageMetadata::default();
+        assert_eq!(metadata.direction("ar-EG"), TextDirection::Rtl);
+        assert_eq!(metadata.direction("he"), TextDirection::Rtl);
+        assert_eq!(metadata.direction("en"), TextDirection::Ltr);
+        assert_eq!(metadata.script("sr-Latn"), "Latn");
+        assert_eq!(metadata.script("sr"), "Cyrl");
+        assert_eq!(metadata.script("zh-TW"), "Hant");
+        assert_eq!(metadata.script("zh_CN"), "Hans");
+        assert_eq!(metadata.script("xx"), "Latn");
+        assert_eq!(metadata.region("es-419"), Some("419"));
+        assert_eq!(metadata.region("sr-Latn-RS"), Some("RS"));
+        assert_eq!(metadata.region("fr"), None);
+        assert_eq!(metadata.english_name("fr-CA"), Some("French"));
+        assert_eq!(metadata.english_name("xx"), None);
+        assert_eq!(metadata.plural_rules("pt-PT"), "pt-PT");
+    }
+
+    #[test]
+    fn test_declared() {
+        let metadata: LanguageMetadata = serde_json::from_str(
+            r#"{"direction": "rtl", "script": "Latn", "region": "US", "plural_rules": "en"}"#,
+        )
+        .unwrap();
+        assert_eq!(metadata.direction("en"), TextDirection::Rtl);
+        assert_eq!(metadata.script("ar"), "Latn");
+        assert_eq!(metadata.region("ar-EG"), Some("US"));
+        assert_eq!(metadata.plural_rules("ar"), "en");
+
+        assert!(serde_json::from_str::<LanguageMetadata>(r#"{"direction": "up"}"#).is_err());
+        assert_eq!(bidi_isolate("abc"), "\u{2068}abc\u{2069}");
+    }
+}
@@ -0,0 +1,276 @@
+use std::borrow::Cow;
+use std::ops::Index;
+
+use crate::{FormatArg, LanguageSet};
+
+/// A borrowed view over a single category of a language set
+/// Every lookup made through the scope is prefixed with the scope's category
+#[derive(Clone)]
+pub struct LanguageScope<'a> {
+    set: &'a LanguageSet,
+    prefix: String,
+}
+
+impl<'a> LanguageScope<'a> {
+    /// Create a new scope over a category of a set
+    ///
+    /// # Arguments
+    /// * `set` - Language set to search
+    /// * `category` - Path to the category, such as `settings\\network`
+    pub fn new(set: &'a LanguageSet, category: &str) -> Self {
+        Self {
+            set,
+            prefix: category.trim_matches('\\').to_string(),
+        }
+    }
+
+    /// Return the category path this scope is restricted to
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Return the set this scope searches
+    pub fn language_set(&self) -> &'a LanguageSet {
+        self.set
+    }
+
+    /// Narrow the scope further into a sub-category
+    ///
+    /// # Arguments
+    /// * `category` - Path to the category, relative to this scope
+    pub fn scope(&self, category: &str) -> LanguageScope<'a> {
+        Self::new(self.set, &self.path(category))
+    }
+
+    /// Return the full path of a name relative to this scope
+    ///
+    /// # Arguments
+    /// * `name` - Name relative to this scope
+    pub fn path(&self, name: &str) -> String {
+        let name = name.trim_matches('\\');
+        if self.prefix.is_empty() {
+            name.to_string()
+        } else if name.is_empty() {
+            self.prefix.clone()
+        } else {
+            format!("{}\\{}", self.prefix, name)
+        }
+    }
+
+    /// Look up a string in a specific language
+    ///
+    /// # Arguments
+    /// * `language` - Language to search
+    /// * `name` - String to find, relative to this scope
+    pub fn get_from_lang(&self, language: &str, name: &str) -> Option<&'a str> {
+        self.set.get_from_lang(language, &self.path(name))
+    }
+
+    /// Look up a string
+    ///
+    /// # Arguments
+    /// * `name` - String to find, relative to this scope
+    pub fn get(&self, name: &str) -> Option<&'a str> {
+        self.set.get(&self.path(name))
+    }
+
+    /// Look up the variant of a string for a selector, such as a grammatical gender
+    ///
+    /// # Arguments
+    /// * `name` - String to find, relative to this scope
+    /// * `selector` - Variant to choose, such as `female`
+    pub fn get_select(&self, name: &str, selector: &str) -> Option<&'a str> {
+        self.set.get_select(&self.path(name), selector)
+    }
+
+    /// Look up the variant of a string for a position, as in "3rd place"
+    ///
+    /// # Arguments
+    /// * `name` - String to find, relative to this scope
+    /// * `n` - Position being written
+    pub fn get_ordinal(&self, name: &str, n: i64) -> Option<&'a str> {
+        self.set.get_ordinal(&self.path(name), n)
+    }
+
+    /// Look up a string, applying the set's missing key policy if it cannot be found
+    ///
+    /// # Arguments
+    /// * `name` - String to find, relative to this scope
+    pub fn lookup(&self, name: &str) -> Cow<'a, str> {
+        let path = self.path(name);
+        match self.set.get(&path) {
+            Some(s) => Cow::Borrowed(s),
+            None => Cow::Owned(self.set.missing_key(&path).into_owned()),
+        }
+    }
+
+    /// Look up a string, and substitute arguments into its placeholders
+    ///
+    /// # Arguments
+    /// * `name` - String to find, relative to this scope
+    /// * `args` - Names and values of the arguments
+    pub fn format(&self, name: &str, args: &[(&str, FormatArg)]) -> Option<String> {
+        self.set.format(&self.path(name), args)
+    }
+
+    /// Look up the variant of a string for a selector, and substitute arguments into its placeholders
+    ///
+    /// # Arguments
+    /// * `name` - String to find, relative to this scope
+    /// * `selector` - Variant to choose, such as `female`
+    /// * `args` - Names and values of the arguments
+    pub fn format_select(
+        &self,
+        name: &str,
+        selector: &str,
+        args: &[(&str, FormatArg)],
+    ) -> Option<String> {
+        self.set.format_select(&self.path(name), selector, args)
+    }
+
+    /// Look up the variant of a string for a position, and substitute arguments into its placeholders
+    ///
+    /// # Arguments
+    /// * `name` - String to find, relative to this scope
+    /// * `n` - Position choosing the variant, as in "3rd place"
+    /// * `args` - Names and values of the arguments
+    pub fn format_ordinal(&self, name: &str, n: i64, args: &[(&str, FormatArg)]) -> Option<String> {
+        self.set.format_ordinal(&self.path(name), n, args)
+    }
+
+    /// Return an embedded resource as a utf8 string
+    pub fn utf8_resource(&self, name: &str) -> Option<&'a str> {
+        self.set.utf8_resource(&self.path(name))
+    }
+
+    /// Return an embedded resource as a slice of bytes
+    pub fn binary_resource(&self, name: &str) -> Option<&'a [u8]> {
+        self.set.binary_resource(&self.path(name))
+    }
+}
+
+impl Index<&str> for LanguageScope<'_> {
+    type Output = str;
+
+    fn index(&self, name: &str) -> &Self::Output {
+        let path = self.path(name);
+        self.set.get(&path).unwrap_or_else(|| {
+            self.set.missing_key(&path);
+            ""
+        })
+    }
+}
+
+#[cfg(test)]
+mod test_token {
+    use super::*;
+    use crate as embedded_lang;
+    use crate::{embedded_language, Language, MissingKeyPolicy};
+    use std::collections::HashMap;
+
+    fn test_set() -> LanguageSet {
+        let mut set = LanguageSet::new(
+            "fr",
+            &[
+                embedded_language!("../examples/en.lang.json"),
+                embedded_language!("../examples/fr.lang.json"),
+            ],
+        );
+        set.set_fallback_language("en");
+        set
+    }
+
+    #[test]
+    fn test_get() {
+        let set = test_set();
+        let scope = set.scope("category");
+
+        assert_eq!(scope.get("category2\\foo"), Some("bar"));
+        assert_eq!(scope.get("tree"), None);
+        assert_eq!(scope["category2\\foo"], "bar".to_string());
+        assert_eq!(scope["nope"], "".to_string());
+    }
+
+    #[test]
+    fn test_lookup() {
+        let mut set = test_set();
+        set.set_missing_key_policy(MissingKeyPolicy::Key);
+        let scope = set.scope("category");
+
+        assert_eq!(scope.lookup("category2\\foo"), "bar");
+        assert_eq!(scope.lookup("nope"), "category\\nope");
+    }
+
+    #[test]
+    fn test_nested_scope() {
+        let set = test_set();
+        let scope = set.scope("category\\").scope("category2");
+
+        assert_eq!(scope.prefix(), "category\\category2");
+        assert_eq!(scope.get("foo"), Some("bar"));
+        assert_eq!(scope.get_from_lang("fr", "foo"), None);
+    }
+
+    #[test]
+    fn test_fallback() {
+        let mut set = test_set();
+        set.add_language(
+            Language::new_from_string(
+                r#"{"name": "Deutsch", "short_name": "de", "strings": {"category": {"category2": {}}, "baum": "baum"}}"#,
+                HashMap::default(),
+            )
+            .unwrap(),
+        );
+        set.set_language("de");
+
+        assert_eq!(set.scope("category\\category2").get("foo"), Some("bar"));
+        assert_eq!(set.scope("").get("baum"), Some("baum"));
+    }
+
+    #[test]
+    fn test_resources() {
+        let mut set = LanguageSet::new(
+            "en",
+            &[embedded_language!("../examples/en.lang.json", resources = [
+                "category\\license_file": "../LICENSE"
+            ])],
+        );
+        set.set_fallback_language("en");
+
+        let scope = set.scope("category");
+        assert!(scope.binary_resource("license_file").is_some());
+        assert!(scope.utf8_resource("license_file").is_some());
+        assert!(scope.binary_resource("category\\license_file").is_none());
+    }
+
+    #[test]
+    fn test_format_variants() {
+        let mut set = LanguageSet::new(
+            "en",
+            &[Language::new_from_string(
+                r#"{"name": "English", "short_name": "en", "strings": {"race": {
+                    "place": {"$ordinal": {"one": "{n}st of {total}", "two": "{n}nd of {total}", "other": "{n}th of {total}"}},
+                    "files": {"$select": {"male": "He has {n} files", "other": "They have {n} files"}}
+                }}}"#,
+                HashMap::default(),
+            )
+            .unwrap()],
+        );
+        set.set_fallback_language("en");
+
+        let scope = set.scope("race");
+        let args = [("total", 21.into()), ("n", 2.into())];
+        assert_eq!(
+            scope.format_ordinal("place", 2, &args),
+            Some("2nd of 21".to_string())
+        );
+        assert_eq!(
+            scope.format_select("files", "male", &[("n", 3.into())]),
+            Some("He has 3 files".to_string())
+        );
+        assert_eq!(
+            scope.format_select("files", "female", &[("n", 3.into())]),
+            Some("They have 3 files".to_string())
+        );
+    }
+}
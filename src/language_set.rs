@@ -1,16 +1,66 @@
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
 use std::ops::Index;
+use std::time::Duration;
 
-use crate::Language;
+use crate::lazy_language::LazyLanguage;
+use crate::serialization::FormatVersion;
+use crate::{
+    CurrencyDisplay, DateLength, DateTime, FormatArg, Language, LanguageLoader, LanguageMetadata,
+    LanguageScope, LanguageStringObject, ListType, ListWidth, LookupEvent, LookupTelemetry,
+    MessageFormatter, MissingKeyCollector, MissingKeyPolicy, NumberStyle, PseudoLocaleOptions,
+    RelativeNumeric, RelativeUnit, ResourceInfo, StringNotes, TextDirection, TranslationState,
+    TranslationStatus, Unit, UnitWidth,
+};
+
+/// A named language stacked on top of another language with the same code
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct LanguageLayer {
+    name: String,
+    language: Language,
+}
+
+impl LanguageLayer {
+    /// Get the name of the layer
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the language in the layer
+    pub fn language(&self) -> &Language {
+        &self.language
+    }
+}
 
 /// A searchable set of language string instances
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct LanguageSet {
+    #[serde(default)]
+    format_version: FormatVersion,
+
     current: String,
     fallback: String,
     languages: HashMap<String, Language>,
+
+    #[serde(default)]
+    layers: HashMap<String, Vec<LanguageLayer>>,
+
+    #[serde(default)]
+    namespaces: HashMap<String, LanguageSet>,
+
+    #[serde(skip)]
+    missing_key_policy: MissingKeyPolicy,
+
+    #[serde(skip)]
+    missing_key_collector: Option<MissingKeyCollector>,
+
+    #[serde(skip)]
+    telemetry: Option<LookupTelemetry>,
+
+    #[serde(skip)]
+    registered: HashMap<String, LazyLanguage>,
 }
 
 impl LanguageSet {
@@ -21,28 +71,39 @@ impl LanguageSet {
     /// * `languages` - Array of language instances to use
     pub fn new(fallback_language: &str, languages: &[Language]) -> Self {
         Self {
+            format_version: FormatVersion,
             current: fallback_language.to_string(),
             fallback: fallback_language.to_string(),
             languages: languages
                 .iter()
                 .map(|l| (l.short_name().to_string(), l.clone()))
                 .collect(),
+            layers: HashMap::default(),
+            namespaces: HashMap::default(),
+            missing_key_policy: MissingKeyPolicy::default(),
+            missing_key_collector: None,
+            telemetry: None,
+            registered: HashMap::default(),
         }
     }
 
     /// List all supported languages
+    /// Registered languages are loaded if they are not loaded yet
     pub fn all_languages(&self) -> Vec<Language> {
-        self.languages.values().cloned().collect()
+        self.codes()
+            .filter_map(|code| self.language(code))
+            .cloned()
+            .collect()
     }
 
     /// Return the set's fallback language
     pub fn fallback_language(&self) -> Option<&Language> {
-        self.languages.get(&self.fallback)
+        self.language(&self.fallback)
     }
 
     /// Return the set's current language
     pub fn current_language(&self) -> Option<&Language> {
-        self.languages.get(&self.current)
+        self.language(&self.current)
     }
 
     /// Add a language to the set
@@ -54,6 +115,184 @@ impl LanguageSet {
             .insert(language.short_name().to_string(), language);
     }
 
+    /// Add a pseudo-localized copy of a language to the set, such as `qps-ploc`
+    /// Returns false if the source language code is not recognized
+    ///
+    /// # Arguments
+    /// * `language` - Language code to derive the pseudo-locale from
+    /// * `options` - Transformations to apply, and the name and code of the new language
+    pub fn add_pseudo_language(&mut self, language: &str, options: &PseudoLocaleOptions) -> bool {
+        match self.language(language) {
+            Some(source) => {
+                let pseudo = source.pseudo_localize(options);
+                self.add_language(pseudo);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Stack a language on top of the language with the same code
+    /// Lookups consult layers from the most recently added down to the base language
+    /// If the set has no language with that code yet, the language becomes the base
+    ///
+    /// # Arguments
+    /// * `name` - Name of the layer, such as `customer_overrides`
+    /// * `language` - Language to stack
+    pub fn add_layer(&mut self, name: &str, language: Language) {
+        let code = language.short_name().to_string();
+        if !self.contains(&code) {
+            self.add_language(language);
+            return;
+        }
+
+        self.layers.entry(code).or_default().push(LanguageLayer {
+            name: name.to_string(),
+            language,
+        });
+    }
+
+    /// Remove a layer from a language
+    ///
+    /// # Arguments
+    /// * `language` - Language code
+    /// * `name` - Name of the layer to remove
+    pub fn remove_layer(&mut self, language: &str, name: &str) -> Option<Language> {
+        let layers = self.layers.get_mut(language)?;
+        let index = layers.iter().position(|l| l.name == name)?;
+        Some(layers.remove(index).language)
+    }
+
+    /// List the layers stacked on a language, from the top down
+    ///
+    /// # Arguments
+    /// * `language` - Language code
+    pub fn layers(&self, language: &str) -> Vec<&LanguageLayer> {
+        self.layers
+            .get(language)
+            .map(|l| l.iter().rev().collect())
+            .unwrap_or_default()
+    }
+
+    /// Report which keys each layer of a language shadows in the layers below it
+    /// Returns the shadowed keys for each layer name, from the top down
+    ///
+    /// # Arguments
+    /// * `language` - Language code
+    pub fn shadowed_keys(&self, language: &str) -> Vec<(String, Vec<String>)> {
+        let layers = self.layers(language);
+        layers
+            .iter()
+            .enumerate()
+            .map(|(i, layer)| {
+                let below = layers[i + 1..]
+                    .iter()
+                    .map(|l| &l.language)
+                    .chain(self.language(language));
+                let shadowed = layer
+                    .language
+                    .leaves()
+                    .filter(|(path, _)| {
+                        let path = path.to_string();
+                        below.clone().any(|l| l.get_object(&path).is_some())
+                    })
+                    .map(|(path, _)| path.to_string())
+                    .collect();
+                (layer.name.clone(), shadowed)
+            })
+            .collect()
+    }
+
+    /// Add a namespace of languages, such as a plugin's translations
+    /// Strings in the namespace are looked up as `namespace:name`, and the namespace starts out
+    /// with the same fallback language as the set
+    /// Replaces any existing namespace with the same name
+    ///
+    /// # Arguments
+    /// * `namespace` - Name of the namespace
+    /// * `languages` - Array of language instances to use
+    pub fn add_namespace(&mut self, namespace: &str, languages: &[Language]) {
+        let mut set = Self::new(&self.fallback, languages);
+        set.current = self.current.clone();
+        self.namespaces.insert(namespace.to_string(), set);
+    }
+
+    /// Remove a namespace, such as when unloading a plugin
+    ///
+    /// # Arguments
+    /// * `namespace` - Name of the namespace
+    pub fn remove_namespace(&mut self, namespace: &str) -> Option<LanguageSet> {
+        self.namespaces.remove(namespace)
+    }
+
+    /// Return a namespace's language set
+    ///
+    /// # Arguments
+    /// * `namespace` - Name of the namespace
+    pub fn namespace(&self, namespace: &str) -> Option<&LanguageSet> {
+        self.namespaces.get(namespace)
+    }
+
+    /// Return a namespace's language set for modification, such as to change its fallback language
+    ///
+    /// # Arguments
+    /// * `namespace` - Name of the namespace
+    pub fn namespace_mut(&mut self, namespace: &str) -> Option<&mut LanguageSet> {
+        self.namespaces.get_mut(namespace)
+    }
+
+    /// List all namespaces in the set
+    pub fn all_namespaces(&self) -> Vec<&str> {
+        self.namespaces.keys().map(|k| k.as_str()).collect()
+    }
+
+    /// Split a `namespace:name` lookup into the namespace's set and the name within it
+    fn split_namespace<'a>(&self, name: &'a str) -> Option<(&LanguageSet, &'a str)> {
+        let (namespace, name) = name.split_once(':')?;
+        self.namespaces.get(namespace).map(|set| (set, name))
+    }
+
+    /// Return every layer of a language, from the top down, ending with the base language
+    fn stack(&self, language: &str) -> impl Iterator<Item = &Language> + Clone {
+        self.layers
+            .get(language)
+            .into_iter()
+            .flat_map(|l| l.iter().rev().map(|l| &l.language))
+            .chain(self.language(language))
+    }
+
+    /// Return a language in the set, loading it if it is registered and not loaded yet
+    fn language(&self, code: &str) -> Option<&Language> {
+        self.languages
+            .get(code)
+            .or_else(|| self.registered.get(code).and_then(|l| l.get().ok()))
+    }
+
+    /// Return a language to modify
+    /// Registered languages are loaded and added to the set, and can no longer be unloaded
+    fn language_mut(&mut self, code: &str) -> Option<&mut Language> {
+        if !self.languages.contains_key(code) {
+            self.registered.get(code)?.get().ok()?;
+            let language = self.registered.remove(code)?.into_language().ok()?;
+            self.languages.insert(code.to_string(), language);
+        }
+        self.languages.get_mut(code)
+    }
+
+    /// Return true if a language is in the set, or registered with it
+    fn contains(&self, code: &str) -> bool {
+        self.languages.contains_key(code) || self.registered.contains_key(code)
+    }
+
+    /// Return the code of every language in the set, including registered languages
+    fn codes(&self) -> impl Iterator<Item = &String> {
+        self.languages.keys().chain(
+            self.registered
+                .keys()
+                .filter(|code| !self.languages.contains_key(*code)),
+        )
+    }
+
     /// Add a language from a JSON file to the set
     ///
     /// # Arguments
@@ -72,12 +311,115 @@ impl LanguageSet {
         }
     }
 
+    /// Add every `.lang.json` file in a directory to the set
+    ///
+    /// # Arguments
+    /// * `path` - Path to the directory
+    pub fn load_directory(&mut self, path: &str) -> Result<(), String> {
+        let mut files = std::fs::read_dir(path)
+            .map_err(|e| e.to_string())?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|p| p.to_string_lossy().ends_with(".lang.json"))
+            .collect::<Vec<_>>();
+        files.sort();
+
+        for file in files {
+            self.load_language(&file.to_string_lossy(), HashMap::default())?;
+        }
+        Ok(())
+    }
+
+    /// Register a language to be loaded the first time it is used, such as by `set_language` or `get_from_lang`
+    /// Languages added to the set directly take precedence over registered languages with the same code
+    /// Registered languages are not serialized with the set
+    ///
+    /// # Arguments
+    /// * `language` - Code of the language
+    /// * `loader` - Where to load the language from
+    pub fn register_language(&mut self, language: &str, loader: LanguageLoader) {
+        self.registered
+            .insert(language.to_string(), LazyLanguage::new(loader));
+    }
+
+    /// Register every `.lang.json` file in a directory, to be loaded the first time each is used
+    /// Languages are registered under the name of their file, such as `fr` for `fr.lang.json`
+    ///
+    /// # Arguments
+    /// * `path` - Path to the directory
+    pub fn register_directory(&mut self, path: &str) -> Result<(), String> {
+        let files = std::fs::read_dir(path)
+            .map_err(|e| e.to_string())?
+            .filter_map(|entry| entry.ok().map(|e| e.path()));
+        for file in files {
+            let name = file.file_name().unwrap_or_default().to_string_lossy();
+            if let Some(code) = name.strip_suffix(".lang.json") {
+                let loader = LanguageLoader::File(file.to_string_lossy().to_string());
+                self.register_language(code, loader);
+            }
+        }
+        Ok(())
+    }
+
+    /// Load a registered language now, instead of on its first use
+    ///
+    /// # Arguments
+    /// * `language` - Code of the language
+    pub fn preload(&self, language: &str) -> Result<(), String> {
+        if self.languages.contains_key(language) {
+            return Ok(());
+        }
+
+        match self.registered.get(language) {
+            Some(lazy) => lazy.get().map(|_| ()).map_err(str::to_string),
+            None => Err(format!("{} is not registered", language)),
+        }
+    }
+
+    /// Return true if a language is loaded
+    /// Languages added to the set directly are always loaded
+    ///
+    /// # Arguments
+    /// * `language` - Code of the language
+    pub fn is_loaded(&self, language: &str) -> bool {
+        self.languages.contains_key(language)
+            || self.registered.get(language).is_some_and(|l| l.is_loaded())
+    }
+
+    /// Unload a registered language, so it is loaded again on its next use
+    /// Returns false if the language is not registered, or not loaded
+    ///
+    /// # Arguments
+    /// * `language` - Code of the language
+    pub fn unload_language(&mut self, language: &str) -> bool {
+        self.registered
+            .get_mut(language)
+            .is_some_and(|l| l.unload())
+    }
+
+    /// Unload every registered language that has not been used for a while
+    /// The current and fallback languages stay loaded
+    /// Returns the codes of the unloaded languages, sorted
+    ///
+    /// # Arguments
+    /// * `idle` - How long a language must go unused to be unloaded
+    pub fn unload_unused(&mut self, idle: Duration) -> Vec<String> {
+        let mut unloaded: Vec<String> = self
+            .registered
+            .iter_mut()
+            .filter(|(code, _)| **code != self.current && **code != self.fallback)
+            .filter(|(_, l)| l.idle_for().is_some_and(|t| t >= idle))
+            .filter_map(|(code, l)| l.unload().then(|| code.clone()))
+            .collect();
+        unloaded.sort();
+        unloaded
+    }
+
     /// Check the completeness of all language packs against the fallback
     /// Returns the list of missing strings for each language
     pub fn verify(&self) -> HashMap<String, Vec<String>> {
         if let Some(fallback) = self
             .fallback_language()
-            .and_then(|l| Some(l.strings().keys().cloned().collect::<HashSet<String>>()))
+            .map(|l| l.strings().keys().cloned().collect::<HashSet<String>>())
         {
             self.languages
                 .iter()
@@ -99,12 +441,179 @@ impl LanguageSet {
         }
     }
 
+    /// Find the strings each language is missing compared to the fallback language
+    /// Strings are compared by full path, and strings provided by a language's layers count as present
+    /// Returns the paths of the missing strings for each language
+    /// Selectors of string variants missing from a language are listed as `path[selector]`
+    pub fn verify_missing(&self) -> HashMap<String, Vec<String>> {
+        if self.fallback_language().is_none() {
+            return HashMap::default();
+        }
+
+        let fallback = self.keys(&self.fallback);
+        self.codes()
+            .map(|code| {
+                let keys = self.keys(code);
+                (code.clone(), fallback.difference(&keys).cloned().collect())
+            })
+            .collect()
+    }
+
+    /// Check every language pack against the constraints in its translator notes, such as `max_length`
+    /// Notes in the fallback language apply to every language, unless a language gives its own
+    /// Returns a description of each problem for each language, such as `menu\save: 12 characters long, over the limit of 10`
+    pub fn verify_notes(&self) -> HashMap<String, Vec<String>> {
+        self.codes()
+            .map(|code| {
+                let mut problems = vec![];
+                let mut seen = HashSet::new();
+                for (path, object) in self.stack(code).flat_map(Language::entries) {
+                    let path = path.to_string();
+                    if object.texts().is_empty() || !seen.insert(path.clone()) {
+                        continue;
+                    }
+
+                    let notes = match (
+                        self.notes_in(code, &path),
+                        self.notes_in(&self.fallback, &path),
+                    ) {
+                        (Some(own), Some(fallback)) => own.or(fallback),
+                        (Some(notes), None) | (None, Some(notes)) => notes.clone(),
+                        (None, None) => continue,
+                    };
+                    let original = (*code != self.fallback)
+                        .then(|| self.get_from_lang(&self.fallback, &path))
+                        .flatten();
+                    if let Some(problem) = object
+                        .texts()
+                        .into_iter()
+                        .find_map(|text| notes.check(text, original))
+                    {
+                        problems.push(format!("{}: {}", path, problem));
+                    }
+                }
+                (code.clone(), problems)
+            })
+            .collect()
+    }
+
+    /// Find the strings in each language translated from text that has since changed in the fallback language
+    /// Only strings whose status records the source text they were translated from are checked
+    /// Returns the paths of the stale strings for each language
+    pub fn verify_status(&self) -> HashMap<String, Vec<String>> {
+        self.codes()
+            .filter(|code| **code != self.fallback)
+            .map(|code| {
+                let mut seen = HashSet::new();
+                let stale = self
+                    .stack(code)
+                    .flat_map(Language::all_statuses)
+                    .filter(|(path, _)| seen.insert(*path))
+                    .filter(|(path, status)| {
+                        self.object_in(&self.fallback, path)
+                            .is_some_and(|source| status.is_stale(source))
+                    })
+                    .map(|(path, _)| path.to_string())
+                    .collect();
+                (code.clone(), stale)
+            })
+            .collect()
+    }
+
+    /// Set the translation status of a string in a language
+    /// The current text of the string in the fallback language is recorded as its source
+    ///
+    /// # Arguments
+    /// * `language` - Code of the language the string was translated into
+    /// * `name` - String to update
+    /// * `state` - New state of the translation
+    pub fn set_status(
+        &mut self,
+        language: &str,
+        name: &str,
+        state: TranslationState,
+    ) -> Result<(), String> {
+        if let Some((namespace, name)) = name.split_once(':') {
+            if let Some(set) = self.namespaces.get_mut(namespace) {
+                return set.set_status(language, name, state);
+            }
+        }
+
+        if self.object_in(language, name).is_none() {
+            return Err(format!("{} does not exist in {}", name, language));
+        }
+
+        let status = TranslationStatus::new(state, self.object_in(&self.fallback, name));
+        self.language_mut(language)
+            .ok_or_else(|| format!("{} is not loaded", language))?
+            .set_status(name, Some(status))
+    }
+
+    /// Mark a string in a language as reviewed against the current text in the fallback language
+    ///
+    /// # Arguments
+    /// * `language` - Code of the language the string was translated into
+    /// * `name` - String to mark
+    pub fn mark_reviewed(&mut self, language: &str, name: &str) -> Result<(), String> {
+        self.set_status(language, name, TranslationState::Reviewed)
+    }
+
+    /// Find the string or category at a path in a language and its layers
+    fn object_in(&self, language: &str, name: &str) -> Option<&LanguageStringObject> {
+        self.stack(language).find_map(|l| l.get_object(name))
+    }
+
+    /// Look up the translator notes for a string in the current language, then the fallback language
+    ///
+    /// # Arguments
+    /// * `name` - String to find
+    pub fn notes(&self, name: &str) -> Option<&StringNotes> {
+        let (set, name) = self.split_namespace(name).unwrap_or((self, name));
+        [&self.current, &set.fallback]
+            .into_iter()
+            .find_map(|language| set.notes_in(language, name))
+    }
+
+    /// Look up the translator notes for a string in a language and its layers
+    fn notes_in(&self, language: &str, name: &str) -> Option<&StringNotes> {
+        self.stack(language).find_map(|l| l.notes(name))
+    }
+
+    /// Check the completeness of a namespace's language packs against the namespace's fallback
+    /// Returns the list of missing strings for each language, or None if the namespace does not exist
+    ///
+    /// # Arguments
+    /// * `namespace` - Namespace to check
+    pub fn verify_namespace(&self, namespace: &str) -> Option<HashMap<String, Vec<String>>> {
+        self.namespaces.get(namespace).map(|n| n.verify_missing())
+    }
+
+    /// Return the path of every string available in a language, including its layers
+    /// Each selector of a string with variants is included as `path[selector]`
+    fn keys(&self, language: &str) -> HashSet<String> {
+        self.stack(language)
+            .flat_map(Language::entries)
+            .flat_map(|(path, object)| match object {
+                LanguageStringObject::Direct(_) | LanguageStringObject::Ordinal(_) => {
+                    vec![path.to_string()]
+                }
+                LanguageStringObject::Select(s) => std::iter::once(path.to_string())
+                    .chain(
+                        s.selectors()
+                            .map(|selector| format!("{}[{}]", path, selector)),
+                    )
+                    .collect(),
+                LanguageStringObject::Category(_) => vec![],
+            })
+            .collect()
+    }
+
     /// Set the fallback language for lookups
     ///
     /// # Arguments
     /// * `language` - New language
     pub fn set_fallback_language(&mut self, language: &str) -> bool {
-        if self.languages.contains_key(language) {
+        if self.language(language).is_some() {
             self.fallback = language.to_string();
             true
         } else {
@@ -117,9 +626,9 @@ impl LanguageSet {
     /// # Arguments
     /// * `language` - New language
     ///
-    /// returns false if the language code is not recognized
+    /// returns false if the language code is not recognized, or a registered language fails to load
     pub fn set_language(&mut self, language: &str) -> bool {
-        if self.languages.contains_key(language) {
+        if self.language(language).is_some() {
             self.current = language.to_string();
             true
         } else {
@@ -133,11 +642,21 @@ impl LanguageSet {
     /// * `language` - Language to search
     /// * `name` - String to find
     pub fn get_from_lang(&self, language: &str, name: &str) -> Option<&str> {
-        if let Some(lang) = self.languages.get(language) {
-            lang.get(name)
-        } else {
-            None
+        self.find_in_lang(language, name, &Language::get)
+    }
+
+    /// Look up a string in a language and its layers, using a function to read each layer
+    fn find_in_lang<'a>(
+        &'a self,
+        language: &str,
+        name: &str,
+        get: &impl Fn(&'a Language, &str) -> Option<&'a str>,
+    ) -> Option<&'a str> {
+        if let Some((namespace, name)) = self.split_namespace(name) {
+            return namespace.find_in_lang(language, name, get);
         }
+
+        self.stack(language).find_map(|l| get(l, name))
     }
 
     /// Look up a string
@@ -145,116 +664,553 @@ impl LanguageSet {
     /// # Arguments
     /// * `name` - String to find
     pub fn get(&self, name: &str) -> Option<&str> {
-        self.current_language()
-            .and_then(|l| l.get(name))
-            .or(self.fallback_language().and_then(|l| l.get(name)))
+        self.find(name, &Language::get)
     }
 
-    /// Return an embedded resource as a utf8 string
-    pub fn utf8_resource(&self, name: &str) -> Option<&str> {
-        self.current_language()
-            .and_then(|l| l.utf8_resource(name))
-            .or(self.fallback_language().and_then(|l| l.utf8_resource(name)))
+    /// Look up the variant of a string for a selector, such as a grammatical gender
+    /// Falls back to the `other` variant; strings without variants are returned as-is
+    ///
+    /// # Arguments
+    /// * `name` - String to find
+    /// * `selector` - Variant to choose, such as `female`
+    pub fn get_select(&self, name: &str, selector: &str) -> Option<&str> {
+        self.find(name, &|l, name| l.get_select(name, selector))
     }
 
-    /// Return an embedded resource as a slice of bytes
-    pub fn binary_resource(&self, name: &str) -> Option<&[u8]> {
-        self.current_language()
-            .and_then(|l| l.binary_resource(name))
-            .or(self
-                .fallback_language()
-                .and_then(|l| l.binary_resource(name)))
+    /// Look up the variant of a string for a position, as in "3rd place"
+    /// The variant is chosen by the ordinal rules of the language the string is found in
+    ///
+    /// # Arguments
+    /// * `name` - String to find
+    /// * `n` - Position being written
+    pub fn get_ordinal(&self, name: &str, n: i64) -> Option<&str> {
+        self.find(name, &|l, name| l.get_ordinal(name, n))
     }
 
-    /// Attach a document to a language
-    pub fn attach<T: Serialize + DeserializeOwned + for<'a> Deserialize<'a>>(
-        &mut self,
-        language: &str,
+    /// Look up a string in the current language, then the fallback language,
+    /// recording telemetry and missing strings
+    fn find<'a>(
+        &'a self,
         name: &str,
-        attachment: T,
-    ) {
-        if let Some(lang) = self.languages.get_mut(language) {
-            lang.attach(name, attachment).ok();
+        get: &impl Fn(&'a Language, &str) -> Option<&'a str>,
+    ) -> Option<&'a str> {
+        let found = self.resolve(name, get);
+        if let Some(telemetry) = &self.telemetry {
+            telemetry.record(&LookupEvent {
+                key: name,
+                language: &self.current,
+                served_by: found.map(|(_, language)| language),
+                fallback: found.is_some_and(|(_, language)| language != self.current),
+            });
         }
+        if let (None, Some(collector)) = (found, &self.missing_key_collector) {
+            collector.record(&self.current, name);
+        }
+        found.map(|(s, _)| s)
     }
 
-    /// Get an attachment
-    pub fn attachment<T: Serialize + DeserializeOwned + for<'a> Deserialize<'a>>(
-        &self,
+    /// Look up a string in the current language, then the fallback language
+    /// Returns the string, and the code of the language it was found in
+    fn resolve<'a>(
+        &'a self,
         name: &str,
-    ) -> Option<T> {
-        self.current_language()
-            .and_then(|l| l.attachment(name))
-            .or(self.fallback_language().and_then(|l| l.attachment(name)))
+        get: &impl Fn(&'a Language, &str) -> Option<&'a str>,
+    ) -> Option<(&'a str, &'a str)> {
+        let (set, name) = self.split_namespace(name).unwrap_or((self, name));
+        [&self.current, &set.fallback]
+            .into_iter()
+            .find_map(|language| {
+                set.find_in_lang(language, name, get)
+                    .map(|s| (s, language.as_str()))
+            })
     }
-}
 
-impl Index<&str> for LanguageSet {
-    type Output = str;
+    /// Count every lookup made through the set
+    ///
+    /// # Arguments
+    /// * `telemetry` - Telemetry to record into, or None to stop recording
+    pub fn set_telemetry(&mut self, telemetry: Option<LookupTelemetry>) {
+        self.telemetry = telemetry;
+    }
 
-    fn index(&self, name: &str) -> &Self::Output {
-        self.get(name).unwrap_or_default()
+    /// Return the set's telemetry, if it is enabled
+    pub fn telemetry(&self) -> Option<&LookupTelemetry> {
+        self.telemetry.as_ref()
     }
-}
 
-#[cfg(test)]
-mod test_token {
-    use super::*;
-    use crate as embedded_lang;
-    use crate::embedded_language;
+    /// Look up a string, applying the set's missing key policy if it cannot be found
+    ///
+    /// # Arguments
+    /// * `name` - String to find
+    pub fn lookup<'a>(&'a self, name: &'a str) -> Cow<'a, str> {
+        match self.get(name) {
+            Some(s) => Cow::Borrowed(s),
+            None => self.missing_key(name),
+        }
+    }
 
-    #[test]
-    fn test_current_language() {
-        let mut set = LanguageSet::new(
-            "fr",
-            &[
-                embedded_language!("../examples/en.lang.json"),
-                embedded_language!("../examples/fr.lang.json"),
-            ],
-        );
+    /// Return the direction the current language is written in
+    /// Languages missing from the set use the direction derived from their code
+    pub fn direction(&self) -> TextDirection {
+        match self.current_language() {
+            Some(language) => language.direction(),
+            None => LanguageMetadata::default().direction(&self.current),
+        }
+    }
 
-        assert_eq!(set.current_language().unwrap().short_name(), "fr");
-        set.set_language("en");
-        assert_eq!(set.current_language().unwrap().short_name(), "en");
+    /// Return true if the current language is written right to left
+    pub fn is_rtl(&self) -> bool {
+        self.direction() == TextDirection::Rtl
     }
 
-    #[test]
-    fn test_fallback_language() {
-        let mut set = LanguageSet::new(
-            "fr",
-            &[
-                embedded_language!("../examples/en.lang.json"),
-                embedded_language!("../examples/fr.lang.json"),
-            ],
-        );
+    /// Look up a string, and substitute arguments into its placeholders
+    /// Arguments are written using the conventions of the current language,
+    /// and wrapped in bidi isolation marks if it is written right to left
+    /// Strings with variants use their `other` variant; see [`LanguageSet::format_select`]
+    /// and [`LanguageSet::format_ordinal`]
+    ///
+    /// # Arguments
+    /// * `name` - String to find
+    /// * `args` - Names and values of the arguments, such as `[("n", 3.into())]`
+    pub fn format(&self, name: &str, args: &[(&str, FormatArg)]) -> Option<String> {
+        self.get(name).map(|s| self.formatter().format(s, args))
+    }
 
-        assert_eq!(set.fallback_language().unwrap().short_name(), "fr");
-        set.set_fallback_language("en");
-        assert_eq!(set.fallback_language().unwrap().short_name(), "en");
+    /// Look up the variant of a string for a selector, and substitute arguments into its placeholders
+    ///
+    /// # Arguments
+    /// * `name` - String to find
+    /// * `selector` - Variant to choose, such as `female`
+    /// * `args` - Names and values of the arguments, such as `[("n", 3.into())]`
+    pub fn format_select(
+        &self,
+        name: &str,
+        selector: &str,
+        args: &[(&str, FormatArg)],
+    ) -> Option<String> {
+        self.get_select(name, selector)
+            .map(|s| self.formatter().format(s, args))
     }
 
-    #[test]
-    fn test_add_language() {
-        let mut set = LanguageSet::new("fr", &[embedded_language!("../examples/fr.lang.json")]);
+    /// Look up the variant of a string for a position, and substitute arguments into its placeholders
+    ///
+    /// # Arguments
+    /// * `name` - String to find
+    /// * `n` - Position choosing the variant, as in "3rd place"
+    /// * `args` - Names and values of the arguments, such as `[("n", 3.into())]`
+    pub fn format_ordinal(&self, name: &str, n: i64, args: &[(&str, FormatArg)]) -> Option<String> {
+        self.get_ordinal(name, n)
+            .map(|s| self.formatter().format(s, args))
+    }
 
-        set.add_language(embedded_language!("../examples/en.lang.json"));
+    /// Return a formatter using the conventions of the current language
+    pub fn formatter(&self) -> MessageFormatter {
+        match self.current_language() {
+            Some(language) => MessageFormatter::for_language(language),
+            None => MessageFormatter::new(&self.current).with_isolation(self.is_rtl()),
+        }
+    }
 
-        assert_eq!(set.set_language("en"), true);
+    /// Write a number using the conventions of the current language
+    ///
+    /// # Arguments
+    /// * `value` - Number to write
+    /// * `style` - How to write the number
+    pub fn format_number(&self, value: f64, style: NumberStyle) -> String {
+        self.formatter().numbers().format(value, style)
     }
 
-    #[test]
-    fn test_load_language() {
-        let mut set = LanguageSet::new("fr", &[embedded_language!("../examples/fr.lang.json")]);
+    /// Write the date part of a date using the conventions of the current language
+    ///
+    /// # Arguments
+    /// * `date` - Date to write
+    /// * `length` - How much detail to write
+    pub fn format_date(&self, date: &DateTime, length: DateLength) -> String {
+        self.formatter().dates().format_date(date, length)
+    }
 
-        assert_eq!(
-            set.load_language("examples/en.lang.json", HashMap::default())
-                .is_ok(),
-            true
-        );
-        assert_eq!(set.set_language("en"), true);
+    /// Write the time part of a date using the conventions of the current language
+    ///
+    /// # Arguments
+    /// * `date` - Date to write
+    /// * `length` - How much detail to write
+    pub fn format_time(&self, date: &DateTime, length: DateLength) -> String {
+        self.formatter().dates().format_time(date, length)
     }
 
-    #[test]
+    /// Write a date and its time of day using the conventions of the current language
+    ///
+    /// # Arguments
+    /// * `date` - Date to write
+    /// * `length` - How much detail to write
+    pub fn format_datetime(&self, date: &DateTime, length: DateLength) -> String {
+        self.formatter().dates().format_datetime(date, length)
+    }
+
+    /// Write an amount of money using the conventions of the current language
+    ///
+    /// # Arguments
+    /// * `value` - Amount to write
+    /// * `code` - ISO 4217 currency code, such as `EUR`
+    /// * `display` - How to identify the currency
+    pub fn format_currency(&self, value: f64, code: &str, display: CurrencyDisplay) -> String {
+        self.formatter().currencies().format(value, code, display)
+    }
+
+    /// Write a number with a unit, using the conventions and plural rules of the current language
+    ///
+    /// # Arguments
+    /// * `value` - Number to write
+    /// * `unit` - Unit of the number
+    /// * `width` - How much space to write the unit in
+    pub fn format_unit(&self, value: f64, unit: Unit, width: UnitWidth) -> String {
+        self.formatter().units().format(value, unit, width)
+    }
+
+    /// Write a number of bytes using the conventions of the current language
+    ///
+    /// # Arguments
+    /// * `bytes` - Number of bytes
+    /// * `width` - How much space to write the unit in
+    pub fn format_bytes(&self, bytes: u64, width: UnitWidth) -> String {
+        self.formatter().units().format_bytes(bytes, width)
+    }
+
+    /// Write a distance in time using the phrases and plural rules of the current language
+    /// Negative values are in the past, and positive values in the future
+    ///
+    /// # Arguments
+    /// * `value` - Distance in time
+    /// * `unit` - Unit of the distance
+    /// * `numeric` - Whether names such as "yesterday" may be used
+    pub fn format_relative(
+        &self,
+        value: f64,
+        unit: RelativeUnit,
+        numeric: RelativeNumeric,
+    ) -> String {
+        self.formatter().relative().format(value, unit, numeric)
+    }
+
+    /// Write the distance from one date to another in the unit best suited to it, such as "3 days ago"
+    ///
+    /// # Arguments
+    /// * `date` - Date to describe
+    /// * `now` - Date the distance is measured from
+    /// * `numeric` - Whether names such as "yesterday" may be used
+    pub fn format_relative_date(
+        &self,
+        date: &DateTime,
+        now: &DateTime,
+        numeric: RelativeNumeric,
+    ) -> String {
+        self.formatter().relative().format_date(date, now, numeric)
+    }
+
+    /// Join a list of items using the conventions of the current language, such as "A, B, and C"
+    ///
+    /// # Arguments
+    /// * `items` - Items to join
+    /// * `ty` - How the items relate to each other
+    /// * `width` - How much space to write the list in
+    pub fn format_list<S: AsRef<str>>(
+        &self,
+        items: &[S],
+        ty: ListType,
+        width: ListWidth,
+    ) -> String {
+        self.formatter().lists().format(items, ty, width)
+    }
+
+    /// Set what lookups return when a string cannot be found
+    ///
+    /// # Arguments
+    /// * `policy` - New missing key policy
+    pub fn set_missing_key_policy(&mut self, policy: MissingKeyPolicy) {
+        self.missing_key_policy = policy;
+    }
+
+    /// Return the set's missing key policy
+    pub fn missing_key_policy(&self) -> &MissingKeyPolicy {
+        &self.missing_key_policy
+    }
+
+    /// Record every string missed by lookups into a collector
+    /// Covers every lookup in the current language, such as `get`, `get_select`, `lookup`, `format`
+    /// and the index operator, but not lookups in a specific language with `get_from_lang`
+    ///
+    /// # Arguments
+    /// * `collector` - Collector to record into, or None to stop recording
+    pub fn set_missing_key_collector(&mut self, collector: Option<MissingKeyCollector>) {
+        self.missing_key_collector = collector;
+    }
+
+    /// Produce the text to use in place of a missing string
+    pub(crate) fn missing_key<'a>(&self, name: &'a str) -> Cow<'a, str> {
+        self.missing_key_policy.resolve(&self.current, name)
+    }
+
+    /// Return a view of the set restricted to a category
+    /// Lookups made through the view are prefixed with the category
+    ///
+    /// # Arguments
+    /// * `category` - Path to the category, such as `settings\\network`
+    pub fn scope(&self, category: &str) -> LanguageScope<'_> {
+        LanguageScope::new(self, category)
+    }
+
+    /// Return an embedded resource as a utf8 string
+    /// See [`LanguageSet::binary_resource`] for the order languages are searched in
+    pub fn utf8_resource(&self, name: &str) -> Option<&str> {
+        let (set, name) = self.split_namespace(name).unwrap_or((self, name));
+        set.resource_stack(&self.current)
+            .find_map(|l| l.utf8_resource(name))
+    }
+
+    /// Return an embedded resource as a slice of bytes
+    /// The current language is searched first, then each less specific language down to the primary
+    /// language, as in `fr-CA` then `fr`, then the fallback language in the same way
+    pub fn binary_resource(&self, name: &str) -> Option<&[u8]> {
+        let (set, name) = self.split_namespace(name).unwrap_or((self, name));
+        set.resource_stack(&self.current)
+            .find_map(|l| l.binary_resource(name))
+    }
+
+    /// Describe an embedded resource, including the language it was found in
+    /// Languages are searched in the same order as [`LanguageSet::binary_resource`]
+    pub fn resource_info(&self, name: &str) -> Option<ResourceInfo> {
+        let (set, name) = self.split_namespace(name).unwrap_or((self, name));
+        set.resource_stack(&self.current)
+            .find_map(|l| l.resource_info(name))
+    }
+
+    /// Describe every resource available to a language, including those from less specific and fallback languages
+    /// Returns the resources sorted by name
+    ///
+    /// # Arguments
+    /// * `language` - Code of the language, such as `fr-CA`
+    pub fn resources(&self, language: &str) -> Vec<ResourceInfo> {
+        let mut resources = HashMap::new();
+        for l in self.resource_stack(language) {
+            for name in l.resource_names() {
+                resources
+                    .entry(name)
+                    .or_insert_with(|| l.resource_info(name));
+            }
+        }
+
+        let mut resources: Vec<ResourceInfo> = resources.into_values().flatten().collect();
+        resources.sort_by(|a, b| a.name.cmp(&b.name));
+        resources
+    }
+
+    /// Check that every language provides its own version of each resource in the fallback language
+    /// Resources provided by a less specific language, such as `fr` for `fr-CA`, count as provided
+    /// Returns the names of the missing resources for each language other than the fallback
+    pub fn verify_resources(&self) -> HashMap<String, Vec<String>> {
+        let fallback: HashSet<&str> = self
+            .stack(&self.fallback)
+            .flat_map(Language::resource_names)
+            .collect();
+        self.codes()
+            .filter(|code| **code != self.fallback)
+            .map(|code| {
+                let own: HashSet<&str> = self
+                    .resource_chain(code)
+                    .flat_map(|code| self.stack(code))
+                    .flat_map(Language::resource_names)
+                    .collect();
+                let missing = fallback.difference(&own).map(|s| s.to_string()).collect();
+                (code.clone(), missing)
+            })
+            .collect()
+    }
+
+    /// Return the codes of a language and each less specific language loaded in the set, as in `fr-CA` then `fr`
+    fn resource_chain<'a>(&'a self, language: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        std::iter::successors(Some(language), |code| {
+            code.rfind(['-', '_']).map(|i| &code[..i])
+        })
+        .filter(|code| self.contains(code))
+    }
+
+    /// Return every language searched for a resource, in order
+    fn resource_stack<'a>(&'a self, language: &'a str) -> impl Iterator<Item = &'a Language> + 'a {
+        self.resource_chain(language)
+            .chain(self.resource_chain(&self.fallback))
+            .flat_map(|code| self.stack(code))
+    }
+
+    /// Attach a document to a language
+    pub fn attach<T: Serialize + DeserializeOwned + for<'a> Deserialize<'a>>(
+        &mut self,
+        language: &str,
+        name: &str,
+        attachment: T,
+    ) {
+        if let Some(lang) = self.language_mut(language) {
+            lang.attach(name, attachment).ok();
+        }
+    }
+
+    /// Get an attachment
+    pub fn attachment<T: Serialize + DeserializeOwned + for<'a> Deserialize<'a>>(
+        &self,
+        name: &str,
+    ) -> Option<T> {
+        self.stack(&self.current)
+            .chain(self.stack(&self.fallback))
+            .find_map(|l| l.attachment(name))
+    }
+}
+
+impl PartialEq for LanguageSet {
+    /// Missing key handling, telemetry and registered languages are runtime configuration, and are not compared
+    fn eq(&self, other: &Self) -> bool {
+        self.current == other.current
+            && self.fallback == other.fallback
+            && self.languages == other.languages
+            && self.layers == other.layers
+            && self.namespaces == other.namespaces
+    }
+}
+
+/// Missing strings are recorded, and handled by the missing key policy; since the index operator
+/// can only return text owned by the set, it returns an empty string in place of any generated text
+impl Index<&str> for LanguageSet {
+    type Output = str;
+
+    fn index(&self, name: &str) -> &Self::Output {
+        self.get(name).unwrap_or_else(|| {
+            self.missing_key(name);
+            ""
+        })
+    }
+}
+
+#[cfg(test)]
+mod test_token {
+    use super::*;
+    use crate as embedded_lang;
+    use crate::embedded_language;
+
+    #[test]
+    fn test_current_language() {
+        let mut set = LanguageSet::new(
+            "fr",
+            &[
+                embedded_language!("../examples/en.lang.json"),
+                embedded_language!("../examples/fr.lang.json"),
+            ],
+        );
+
+        assert_eq!(set.current_language().unwrap().short_name(), "fr");
+        set.set_language("en");
+        assert_eq!(set.current_language().unwrap().short_name(), "en");
+    }
+
+    #[test]
+    fn test_fallback_language() {
+        let mut set = LanguageSet::new(
+            "fr",
+            &[
+                embedded_language!("../examples/en.lang.json"),
+                embedded_language!("../examples/fr.lang.json"),
+            ],
+        );
+
+        assert_eq!(set.fallback_language().unwrap().short_name(), "fr");
+        set.set_fallback_language("en");
+        assert_eq!(set.fallback_language().unwrap().short_name(), "en");
+    }
+
+    #[test]
+    fn test_add_language() {
+        let mut set = LanguageSet::new("fr", &[embedded_language!("../examples/fr.lang.json")]);
+
+        set.add_language(embedded_language!("../examples/en.lang.json"));
+
+        assert!(set.set_language("en"));
+    }
+
+    #[test]
+    fn test_load_language() {
+        let mut set = LanguageSet::new("fr", &[embedded_language!("../examples/fr.lang.json")]);
+
+        assert!(set
+            .load_language("examples/en.lang.json", HashMap::default())
+            .is_ok());
+        assert!(set.set_language("en"));
+    }
+
+    #[test]
+    fn test_load_directory() {
+        let mut set = LanguageSet::new("en", &[]);
+
+        assert!(set.load_directory("examples").is_ok());
+        assert!(set.set_language("fr"));
+        assert_eq!(set.get("tree"), Some("arbre"));
+        assert!(set.utf8_resource("welcome").is_some());
+        assert!(set.load_directory("nope").is_err());
+    }
+
+    #[test]
+    fn test_register_language() {
+        let mut set = LanguageSet::new("en", &[]);
+        set.register_language(
+            "en",
+            LanguageLoader::Embedded(include_bytes!("../examples/en.lang.json")),
+        );
+        set.register_language(
+            "fr",
+            LanguageLoader::File("examples/fr.lang.json".to_string()),
+        );
+        set.register_language("de", LanguageLoader::File("nope.lang.json".to_string()));
+        assert!(!set.is_loaded("en"));
+        assert!(!set.is_loaded("fr"));
+
+        assert_eq!(set.get_from_lang("fr", "tree"), Some("arbre"));
+        assert!(set.is_loaded("fr"));
+        assert!(!set.is_loaded("en"));
+
+        assert!(set.set_language("fr"));
+        assert_eq!(set.get("hello_msg"), Some("Bonjour à tous!"));
+        assert!(set.utf8_resource("welcome").is_some());
+        assert!(!set.set_language("de"));
+        assert!(set.preload("de").is_err());
+        assert!(set.preload("es").is_err());
+        assert!(set.preload("en").is_ok());
+
+        assert_eq!(set.unload_unused(Duration::ZERO), Vec::<String>::new());
+        assert!(!set.unload_language("de"));
+        assert!(set.set_language("en"));
+        assert_eq!(
+            set.unload_unused(Duration::from_secs(60)),
+            Vec::<String>::new()
+        );
+        assert_eq!(set.unload_unused(Duration::ZERO), vec!["fr".to_string()]);
+        assert!(!set.is_loaded("fr"));
+        assert!(set.unload_language("en"));
+        assert!(!set.unload_language("en"));
+        assert_eq!(set.get("tree"), Some("tree"));
+
+        set.mark_reviewed("fr", "tree").unwrap();
+        assert!(!set.unload_language("fr"));
+        assert!(set.is_loaded("fr"));
+    }
+
+    #[test]
+    fn test_register_directory() {
+        let mut set = LanguageSet::new("en", &[]);
+        assert!(set.register_directory("examples").is_ok());
+        assert!(set.register_directory("nope").is_err());
+        assert!(!set.is_loaded("en"));
+        assert_eq!(set.all_languages().len(), 2);
+        assert!(set.is_loaded("en"));
+        assert!(set.verify_missing()["fr"].contains(&"mustard".to_string()));
+    }
+
+    #[test]
     fn test_set_fallback_language() {
         let mut set = LanguageSet::new(
             "fr",
@@ -264,10 +1220,10 @@ mod test_token {
             ],
         );
 
-        assert_eq!(set.set_fallback_language("en"), true);
+        assert!(set.set_fallback_language("en"));
         assert_eq!(set.fallback_language().unwrap().short_name(), "en");
 
-        assert_eq!(set.set_fallback_language("foo"), false);
+        assert!(!set.set_fallback_language("foo"));
         assert_eq!(set.fallback_language().unwrap().short_name(), "en");
     }
 
@@ -281,10 +1237,10 @@ mod test_token {
             ],
         );
 
-        assert_eq!(set.set_language("en"), true);
+        assert!(set.set_language("en"));
         assert_eq!(set.current_language().unwrap().short_name(), "en");
 
-        assert_eq!(set.set_language("foo"), false);
+        assert!(!set.set_language("foo"));
         assert_eq!(set.current_language().unwrap().short_name(), "en");
     }
 
@@ -335,4 +1291,630 @@ mod test_token {
         assert_eq!(set["mustard"], "mustard".to_string());
         assert_eq!(set["nope"], "".to_string());
     }
+
+    #[test]
+    fn test_layers() {
+        let mut set = LanguageSet::new(
+            "fr",
+            &[
+                embedded_language!("../examples/en.lang.json"),
+                embedded_language!("../examples/fr.lang.json"),
+            ],
+        );
+        set.set_fallback_language("en");
+
+        let plugin = Language::new_from_string(
+            r#"{"name": "Français", "short_name": "fr", "strings": {"tree": "sapin", "mustard": "moutarde"}}"#,
+            HashMap::default(),
+        )
+        .unwrap();
+        let customer = Language::new_from_string(
+            r#"{"name": "Français", "short_name": "fr", "strings": {"tree": "chêne"}}"#,
+            HashMap::default(),
+        )
+        .unwrap();
+        set.add_layer("plugin", plugin);
+        set.add_layer("customer", customer);
+
+        assert_eq!(set.get("tree"), Some("chêne"));
+        assert_eq!(set.get("mustard"), Some("moutarde"));
+        assert_eq!(set.get("hello_msg"), Some("Bonjour à tous!"));
+        assert_eq!(set.get_from_lang("en", "tree"), Some("tree"));
+
+        let layers: Vec<&str> = set.layers("fr").iter().map(|l| l.name()).collect();
+        assert_eq!(layers, vec!["customer", "plugin"]);
+        assert_eq!(
+            set.shadowed_keys("fr"),
+            vec![
+                ("customer".to_string(), vec!["tree".to_string()]),
+                ("plugin".to_string(), vec!["tree".to_string()]),
+            ]
+        );
+
+        assert!(set.remove_layer("fr", "customer").is_some());
+        assert!(set.remove_layer("fr", "customer").is_none());
+        assert_eq!(set.get("tree"), Some("sapin"));
+    }
+
+    #[test]
+    fn test_verify() {
+        let set = LanguageSet::new(
+            "fr",
+            &[
+                embedded_language!("../examples/en.lang.json"),
+                embedded_language!("../examples/fr.lang.json"),
+            ],
+        );
+
+        let report = set.verify();
+        let mut extra = report["en"].clone();
+        extra.sort();
+        assert_eq!(extra, vec!["category\\category2\\foo", "mustard"]);
+        assert!(report["fr"].is_empty());
+    }
+
+    #[test]
+    fn test_verify_missing() {
+        let mut set = LanguageSet::new(
+            "fr",
+            &[
+                embedded_language!("../examples/en.lang.json"),
+                embedded_language!("../examples/fr.lang.json"),
+            ],
+        );
+        set.set_fallback_language("en");
+
+        let report = set.verify_missing();
+        let mut missing = report["fr"].clone();
+        missing.sort();
+        assert_eq!(missing, vec!["category\\category2\\foo", "mustard"]);
+        assert!(report["en"].is_empty());
+    }
+
+    fn select_set() -> LanguageSet {
+        let mut set = LanguageSet::new(
+            "fr",
+            &[
+                Language::new_from_string(
+                    r#"{"name": "English", "short_name": "en", "strings": {
+                        "welcome": {"$select": {"male": "Welcome, sir", "female": "Welcome, madam", "other": "Welcome"}},
+                        "farewell": {"$select": {"male": "Goodbye, sir", "other": "Goodbye"}}
+                    }}"#,
+                    HashMap::default(),
+                )
+                .unwrap(),
+                Language::new_from_string(
+                    r#"{"name": "Français", "short_name": "fr", "strings": {
+                        "welcome": {"$select": {"male": "Bienvenu", "other": "Bienvenue"}}
+                    }}"#,
+                    HashMap::default(),
+                )
+                .unwrap(),
+            ],
+        );
+        set.set_fallback_language("en");
+        set
+    }
+
+    #[test]
+    fn test_get_select() {
+        let set = select_set();
+        assert_eq!(set.get_select("welcome", "male"), Some("Bienvenu"));
+        assert_eq!(set.get_select("welcome", "female"), Some("Bienvenue"));
+        assert_eq!(set.get_select("farewell", "male"), Some("Goodbye, sir"));
+        assert_eq!(set.get_select("nope", "male"), None);
+        assert_eq!(set.get("welcome"), Some("Bienvenue"));
+        assert_eq!(set.get_select("tree", "male"), None);
+        assert_eq!(
+            set.format_select("welcome", "male", &[]),
+            Some("Bienvenu".to_string())
+        );
+        assert_eq!(set.format_select("nope", "male", &[]), None);
+    }
+
+    #[test]
+    fn test_get_ordinal() {
+        let mut set = LanguageSet::new(
+            "fr",
+            &[
+                Language::new_from_string(
+                    r#"{"name": "English", "short_name": "en", "strings": {
+                        "place": {"$ordinal": {"one": "{n}st place", "two": "{n}nd place", "few": "{n}rd place", "other": "{n}th place"}},
+                        "floor": {"$ordinal": {"one": "{n}st floor", "two": "{n}nd floor", "few": "{n}rd floor", "other": "{n}th floor"}}
+                    }}"#,
+                    HashMap::default(),
+                )
+                .unwrap(),
+                Language::new_from_string(
+                    r#"{"name": "Français", "short_name": "fr", "strings": {
+                        "place": {"$ordinal": {"one": "{n}re place", "other": "{n}e place"}}
+                    }}"#,
+                    HashMap::default(),
+                )
+                .unwrap(),
+            ],
+        );
+        set.set_fallback_language("en");
+
+        assert_eq!(set.get_ordinal("place", 1), Some("{n}re place"));
+        assert_eq!(set.get_ordinal("place", 2), Some("{n}e place"));
+        assert_eq!(set.get_ordinal("floor", 2), Some("{n}nd floor"));
+        assert_eq!(set.get_ordinal("floor", 13), Some("{n}th floor"));
+        assert_eq!(set.get_ordinal("floor", 23), Some("{n}rd floor"));
+        assert_eq!(set.get_ordinal("nope", 1), None);
+        assert_eq!(set.get("place"), Some("{n}e place"));
+
+        set.set_language("en");
+        assert_eq!(
+            set.format_ordinal("place", 21, &[("n", 21.into())]),
+            Some("21st place".to_string())
+        );
+        assert_eq!(
+            set.format_ordinal("place", 2, &[("total", 21.into()), ("n", 2.into())]),
+            Some("2nd place".to_string())
+        );
+        assert_eq!(set.get_ordinal("place", 21), Some("{n}st place"));
+    }
+
+    #[test]
+    fn test_verify_selectors() {
+        let set = select_set();
+        let mut missing = set.verify_missing()["fr"].clone();
+        missing.sort();
+        assert_eq!(
+            missing,
+            vec!["farewell", "farewell[male]", "welcome[female]"]
+        );
+    }
+
+    #[test]
+    fn test_namespaces() {
+        let mut set = LanguageSet::new(
+            "fr",
+            &[
+                embedded_language!("../examples/en.lang.json"),
+                embedded_language!("../examples/fr.lang.json"),
+            ],
+        );
+        set.set_fallback_language("en");
+        set.add_namespace(
+            "plugin_x",
+            &[
+                Language::new_from_string(
+                    r#"{"name": "Français", "short_name": "fr", "strings": {"tree": "sapin"}}"#,
+                    HashMap::default(),
+                )
+                .unwrap(),
+                Language::new_from_string(
+                    r#"{"name": "English", "short_name": "en", "strings": {"tree": "pine", "hello_msg": "hi"}}"#,
+                    HashMap::default(),
+                )
+                .unwrap(),
+            ],
+        );
+
+        assert_eq!(set.get("plugin_x:tree"), Some("sapin"));
+        assert_eq!(set.get("plugin_x:hello_msg"), Some("hi"));
+        assert_eq!(set.get("plugin_x:mustard"), None);
+        assert_eq!(set.get("tree"), Some("arbre"));
+        assert_eq!(set.get("plugin_y:tree"), None);
+        assert_eq!(set.get_from_lang("en", "plugin_x:tree"), Some("pine"));
+        assert_eq!(set.all_namespaces(), vec!["plugin_x"]);
+
+        assert_eq!(
+            set.verify_namespace("plugin_x").unwrap()["fr"],
+            vec!["hello_msg"]
+        );
+
+        set.namespace_mut("plugin_x")
+            .unwrap()
+            .set_fallback_language("fr");
+        assert_eq!(set.get("plugin_x:hello_msg"), None);
+
+        assert!(set.remove_namespace("plugin_x").is_some());
+        assert_eq!(set.get("plugin_x:tree"), None);
+        assert!(set.verify_namespace("plugin_x").is_none());
+    }
+
+    #[test]
+    fn test_lookup() {
+        let mut set = LanguageSet::new(
+            "fr",
+            &[
+                embedded_language!("../examples/en.lang.json"),
+                embedded_language!("../examples/fr.lang.json"),
+            ],
+        );
+        set.set_fallback_language("en");
+        assert_eq!(set.lookup("tree"), "arbre");
+        assert_eq!(set.lookup("nope"), "");
+
+        set.set_missing_key_policy(MissingKeyPolicy::Key);
+        assert_eq!(set.lookup("nope"), "nope");
+
+        set.set_missing_key_policy(MissingKeyPolicy::Marker);
+        assert_eq!(set.lookup("nope"), "⟦nope⟧");
+        assert_eq!(set["nope"], "".to_string());
+
+        set.set_missing_key_policy(MissingKeyPolicy::callback(|l, k| format!("{}/{}", l, k)));
+        assert_eq!(set.lookup("nope"), "fr/nope");
+    }
+
+    #[test]
+    fn test_missing_key_collector() {
+        let mut set = LanguageSet::new(
+            "fr",
+            &[
+                embedded_language!("../examples/en.lang.json"),
+                embedded_language!("../examples/fr.lang.json"),
+            ],
+        );
+        set.set_fallback_language("en");
+
+        let collector = MissingKeyCollector::new();
+        set.set_missing_key_collector(Some(collector.clone()));
+        set.lookup("tree");
+        set.lookup("nope");
+        let _ = &set["nope2"];
+        set.get("nope3");
+        set.get_select("nope4", "male");
+        set.get_ordinal("nope5", 1);
+        set.format("nope6", &[]);
+
+        let missed: Vec<(String, String)> = collector
+            .missed()
+            .into_iter()
+            .map(|m| (m.language, m.key))
+            .collect();
+        assert_eq!(
+            missed,
+            vec![
+                ("fr".to_string(), "nope".to_string()),
+                ("fr".to_string(), "nope2".to_string()),
+                ("fr".to_string(), "nope3".to_string()),
+                ("fr".to_string(), "nope4".to_string()),
+                ("fr".to_string(), "nope5".to_string()),
+                ("fr".to_string(), "nope6".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn test_missing_key_panic() {
+        let mut set = LanguageSet::new("en", &[embedded_language!("../examples/en.lang.json")]);
+        set.set_missing_key_policy(MissingKeyPolicy::Panic);
+        let _ = &set["nope"];
+    }
+
+    #[test]
+    fn test_telemetry() {
+        let mut set = LanguageSet::new(
+            "fr",
+            &[
+                embedded_language!("../examples/en.lang.json"),
+                embedded_language!("../examples/fr.lang.json"),
+            ],
+        );
+        set.set_fallback_language("en");
+        set.add_namespace(
+            "plugin_x",
+            &[embedded_language!("../examples/en.lang.json")],
+        );
+
+        let telemetry = LookupTelemetry::new();
+        set.set_telemetry(Some(telemetry.clone()));
+        set.get("tree");
+        set.get("mustard");
+        set.lookup("nope");
+        let _ = &set["mustard"];
+        set.get("plugin_x:tree");
+
+        let snapshot = telemetry.snapshot();
+        assert_eq!(snapshot.lookups, 5);
+        assert_eq!(snapshot.fallback, 3);
+        assert_eq!(snapshot.missed, 1);
+        assert_eq!(snapshot.keys["mustard"].lookups, 2);
+        assert_eq!(snapshot.keys["mustard"].fallback, 2);
+        assert_eq!(snapshot.languages["fr"], 1);
+        assert_eq!(snapshot.languages["en"], 3);
+
+        set.set_telemetry(None);
+        set.get("tree");
+        assert_eq!(telemetry.snapshot().lookups, 5);
+    }
+
+    #[test]
+    fn test_verify_notes() {
+        let en = Language::new_from_string(
+            r#"{"name": "English", "short_name": "en",
+                "strings": {"save": "Save", "brand": "Acme", "tree": "tree"},
+                "notes": {"save": {"context": "button", "max_length": 6}, "brand": {"do_not_translate": true}}
+            }"#,
+            HashMap::default(),
+        )
+        .unwrap();
+        let de = Language::new_from_string(
+            r#"{"name": "Deutsch", "short_name": "de",
+                "strings": {"save": "Speichern", "brand": "Acme", "tree": "Baum"}
+            }"#,
+            HashMap::default(),
+        )
+        .unwrap();
+        let fr = Language::new_from_string(
+            r#"{"name": "Français", "short_name": "fr",
+                "strings": {"save": "Enregistrer", "brand": "Acmé"},
+                "notes": {"save": {"max_length": 12}}
+            }"#,
+            HashMap::default(),
+        )
+        .unwrap();
+        let mut set = LanguageSet::new("de", &[en, de, fr]);
+        set.set_fallback_language("en");
+
+        let report = set.verify_notes();
+        assert!(report["en"].is_empty());
+        assert_eq!(
+            report["de"],
+            vec!["save: 9 characters long, over the limit of 6"]
+        );
+        assert_eq!(
+            report["fr"],
+            vec!["brand: translated, but marked as do not translate"]
+        );
+
+        assert_eq!(
+            set.notes("save").unwrap().context.as_deref(),
+            Some("button")
+        );
+        set.set_language("fr");
+        assert_eq!(set.notes("save").unwrap().max_length, Some(12));
+        assert!(set.notes("tree").is_none());
+    }
+
+    #[test]
+    fn test_verify_status() {
+        let mut set = LanguageSet::new(
+            "fr",
+            &[
+                embedded_language!("../examples/en.lang.json"),
+                embedded_language!("../examples/fr.lang.json"),
+            ],
+        );
+        set.set_fallback_language("en");
+
+        set.mark_reviewed("fr", "tree").unwrap();
+        set.set_status("fr", "hello_msg", TranslationState::Machine)
+            .unwrap();
+        assert!(set
+            .set_status("fr", "mustard", TranslationState::Fuzzy)
+            .is_err());
+        assert!(set.mark_reviewed("de", "tree").is_err());
+
+        let status = set.current_language().unwrap().status("tree").unwrap();
+        assert_eq!(status.state, TranslationState::Reviewed);
+        assert!(status.source_hash.is_some());
+        assert!(set.verify_status()["fr"].is_empty());
+        assert!(!set.verify_status().contains_key("en"));
+
+        let mut en = embedded_language!("../examples/en.lang.json");
+        en.set("tree", "oak tree").unwrap();
+        set.add_language(en);
+        assert_eq!(set.verify_status()["fr"], vec!["tree"]);
+
+        set.mark_reviewed("fr", "tree").unwrap();
+        assert!(set.verify_status()["fr"].is_empty());
+    }
+
+    #[test]
+    fn test_resource_fallback() {
+        let language = |code: &str, resources: &[(&str, &[u8])]| {
+            Language::new(
+                code.to_string(),
+                code.to_string(),
+                HashMap::default(),
+                resources
+                    .iter()
+                    .map(|(name, bytes)| (name.to_string(), bytes.to_vec()))
+                    .collect(),
+            )
+        };
+        let mut set = LanguageSet::new(
+            "fr-CA",
+            &[
+                language("en", &[("help.pdf", b"%PDF-en"), ("logo.png", b"logo")]),
+                language("fr", &[("help.pdf", b"%PDF-fr")]),
+                language("fr-CA", &[]),
+            ],
+        );
+        set.set_fallback_language("en");
+
+        assert_eq!(set.binary_resource("help.pdf"), Some(&b"%PDF-fr"[..]));
+        assert_eq!(set.utf8_resource("logo.png"), Some("logo"));
+        assert_eq!(set.binary_resource("nope"), None);
+
+        let info = set.resource_info("help.pdf").unwrap();
+        assert_eq!(info.language, "fr");
+        assert_eq!(info.mime_type, "application/pdf");
+        assert_eq!(info.size, 7);
+
+        let resources: Vec<(String, String)> = set
+            .resources("fr-CA")
+            .into_iter()
+            .map(|r| (r.name, r.language))
+            .collect();
+        assert_eq!(
+            resources,
+            vec![
+                ("help.pdf".to_string(), "fr".to_string()),
+                ("logo.png".to_string(), "en".to_string())
+            ]
+        );
+
+        let report = set.verify_resources();
+        assert_eq!(report["fr"], vec!["logo.png"]);
+        assert_eq!(report["fr-CA"], vec!["logo.png"]);
+        assert!(!report.contains_key("en"));
+    }
+
+    #[test]
+    fn test_direction() {
+        let mut he = Language::new_from_string(
+            r#"{"name": "עברית", "short_name": "he", "strings": {}}"#,
+            HashMap::default(),
+        )
+        .unwrap();
+        he.set("welcome", "שלום {name}").unwrap();
+        let mut set = LanguageSet::new("he", &[embedded_language!("../examples/en.lang.json"), he]);
+        set.set_fallback_language("en");
+
+        assert!(set.is_rtl());
+        assert_eq!(
+            set.format("welcome", &[("name", "Ana".into())]).unwrap(),
+            "שלום \u{2068}Ana\u{2069}"
+        );
+
+        set.set_language("en");
+        assert_eq!(set.direction(), TextDirection::Ltr);
+        assert!(!set.set_language("ar"));
+        assert!(!set.is_rtl());
+    }
+
+    #[test]
+    fn test_format() {
+        let mut en = embedded_language!("../examples/en.lang.json");
+        let mut fr = embedded_language!("../examples/fr.lang.json");
+        en.set("score", "{name} scored {n} ({p, number, percent})")
+            .unwrap();
+        fr.set("score", "{name} a marqué {n} ({p, number, percent})")
+            .unwrap();
+        let mut set = LanguageSet::new("en", &[en, fr]);
+
+        let args = [
+            ("name", "Ana".into()),
+            ("n", 1234.5.into()),
+            ("p", 0.25.into()),
+        ];
+        assert_eq!(
+            set.format("score", &args).unwrap(),
+            "Ana scored 1,234.5 (25%)"
+        );
+        assert_eq!(set.format("nope", &args), None);
+
+        set.set_language("fr");
+        assert_eq!(
+            set.format("score", &args).unwrap(),
+            "Ana a marqué 1\u{202F}234,5 (25\u{202F}%)"
+        );
+        assert_eq!(set.format_number(0.5, NumberStyle::Percent), "50\u{202F}%");
+    }
+
+    #[test]
+    fn test_format_date() {
+        let mut fr = embedded_language!("../examples/fr.lang.json");
+        fr.set("updated", "Mis à jour le {d, date, long}").unwrap();
+        let mut set = LanguageSet::new("fr", &[embedded_language!("../examples/en.lang.json"), fr]);
+        let date = DateTime::new(2025, 8, 1).unwrap();
+
+        assert_eq!(
+            set.format("updated", &[("d", date.into())]).unwrap(),
+            "Mis à jour le 1 août 2025"
+        );
+        assert_eq!(set.format_date(&date, DateLength::Short), "01/08/2025");
+
+        set.set_language("en");
+        assert_eq!(
+            set.format_datetime(&date, DateLength::Short),
+            "8/1/25, 12:00 AM"
+        );
+    }
+
+    #[test]
+    fn test_format_currency() {
+        let mut fr = embedded_language!("../examples/fr.lang.json");
+        fr.locale_mut()
+            .currency_symbols
+            .insert("CAD".to_string(), "$".to_string());
+        let mut set = LanguageSet::new("fr", &[embedded_language!("../examples/en.lang.json"), fr]);
+
+        assert_eq!(
+            set.format_currency(1234.5, "CAD", CurrencyDisplay::Symbol),
+            "1\u{202F}234,50\u{A0}$"
+        );
+        assert_eq!(
+            set.format_unit(2.0, Unit::Kilometer, UnitWidth::Long),
+            "2 kilomètres"
+        );
+
+        set.set_language("en");
+        assert_eq!(
+            set.format_currency(1234.5, "CAD", CurrencyDisplay::Symbol),
+            "CA$1,234.50"
+        );
+        assert_eq!(set.format_bytes(2048, UnitWidth::Short), "2 kB");
+    }
+
+    #[test]
+    fn test_invalid_locale() {
+        let mut fr = embedded_language!("../examples/fr.lang.json");
+        fr.locale_mut().dates.months = Some(vec![]);
+        let set = LanguageSet::new("fr", &[fr]);
+        let date = DateTime::new(2025, 3, 6).unwrap();
+        assert_eq!(set.format_date(&date, DateLength::Long), "6 mars 2025");
+
+        let json = r#"{"current": "fr", "fallback": "fr", "languages": {"fr": {
+            "name": "Français", "short_name": "fr", "strings": {"d": "{d, date, long}"},
+            "locale": {"dates": {"months": ["a"]}}}}}"#;
+        let error = serde_json::from_str::<LanguageSet>(json).unwrap_err();
+        assert!(error.to_string().contains("months has 1 names"));
+    }
+
+    #[test]
+    fn test_format_relative() {
+        let mut set = LanguageSet::new(
+            "fr",
+            &[
+                embedded_language!("../examples/en.lang.json"),
+                embedded_language!("../examples/fr.lang.json"),
+            ],
+        );
+        let now = DateTime::new(2025, 6, 10).unwrap();
+        let then = DateTime::new(2025, 6, 7).unwrap();
+
+        assert_eq!(
+            set.format_relative(-1.0, RelativeUnit::Day, RelativeNumeric::Auto),
+            "hier"
+        );
+        assert_eq!(
+            set.format_relative_date(&then, &now, RelativeNumeric::Always),
+            "il y a 3 jours"
+        );
+
+        set.set_language("en");
+        assert_eq!(
+            set.format_relative(2.0, RelativeUnit::Week, RelativeNumeric::Auto),
+            "in 2 weeks"
+        );
+    }
+
+    #[test]
+    fn test_format_list() {
+        let mut en = embedded_language!("../examples/en.lang.json");
+        en.set("invited", "{names} were invited").unwrap();
+        let mut set = LanguageSet::new("fr", &[en, embedded_language!("../examples/fr.lang.json")]);
+        let names = ["Alice", "Bob", "Carol"];
+
+        assert_eq!(
+            set.format_list(&names, ListType::And, ListWidth::Long),
+            "Alice, Bob et Carol"
+        );
+
+        set.set_language("en");
+        assert_eq!(
+            set.format("invited", &[("names", names[..].into())])
+                .unwrap(),
+            "Alice, Bob, and Carol were invited"
+        );
+    }
 }
@@ -0,0 +1,185 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+
+use crate::Language;
+
+/// Function producing a language the first time it is used
+pub type LanguageLoaderCallback = Arc<dyn Fn() -> Result<Language, String> + Send + Sync>;
+
+/// Where a language registered with a set is loaded from when it is first used
+#[derive(Clone)]
+pub enum LanguageLoader {
+    /// Read a `.lang.json` file, along with the resources it declares
+    File(String),
+
+    /// Parse JSON embedded in the binary, such as with `include_bytes!`
+    Embedded(&'static [u8]),
+
+    /// Call a function to produce the language
+    Callback(LanguageLoaderCallback),
+}
+
+impl LanguageLoader {
+    /// Create a loader calling the given function
+    ///
+    /// # Arguments
+    /// * `callback` - Function producing the language
+    pub fn callback<F: Fn() -> Result<Language, String> + Send + Sync + 'static>(
+        callback: F,
+    ) -> Self {
+        Self::Callback(Arc::new(callback))
+    }
+
+    /// Load the language
+    pub fn load(&self) -> Result<Language, String> {
+        match self {
+            Self::File(path) => Language::new_from_file(path, HashMap::default()),
+            Self::Embedded(bytes) => {
+                let json = std::str::from_utf8(bytes).map_err(|e| e.to_string())?;
+                Language::new_from_string(json, HashMap::default())
+            }
+            Self::Callback(callback) => callback(),
+        }
+    }
+}
+
+impl fmt::Debug for LanguageLoader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::File(path) => write!(f, "File({:?})", path),
+            Self::Embedded(bytes) => write!(f, "Embedded({} bytes)", bytes.len()),
+            Self::Callback(_) => write!(f, "Callback"),
+        }
+    }
+}
+
+/// A language registered with a set, parsed the first time it is used
+/// Loading happens once, even when several threads use the language at the same time
+/// A language that fails to load reports the same error until it is unloaded
+#[derive(Debug)]
+pub(crate) struct LazyLanguage {
+    loader: LanguageLoader,
+    language: OnceLock<Result<Language, String>>,
+
+    /// When the registration was created, which `last_used` is measured from
+    created: Instant,
+
+    /// Milliseconds from `created` to the last use, plus one, or zero if it was never used
+    last_used: AtomicU64,
+}
+
+impl LazyLanguage {
+    /// Register a language without loading it
+    ///
+    /// # Arguments
+    /// * `loader` - Where to load the language from
+    pub fn new(loader: LanguageLoader) -> Self {
+        Self {
+            loader,
+            language: OnceLock::new(),
+            created: Instant::now(),
+            last_used: AtomicU64::new(0),
+        }
+    }
+
+    /// Return the language, loading it if this is its first use
+    pub fn get(&self) -> Result<&Language, &str> {
+        let elapsed = self.created.elapsed().as_millis() as u64;
+        self.last_used.store(elapsed + 1, Ordering::Relaxed);
+        self.language
+            .get_or_init(|| self.loader.load())
+            .as_ref()
+            .map_err(String::as_str)
+    }
+
+    /// Load the language if needed, and take it out of the registration
+    pub fn into_language(self) -> Result<Language, String> {
+        match self.language.into_inner() {
+            Some(result) => result,
+            None => self.loader.load(),
+        }
+    }
+
+    /// Return true if the language is loaded
+    pub fn is_loaded(&self) -> bool {
+        matches!(self.language.get(), Some(Ok(_)))
+    }
+
+    /// Return how long ago the language was last used, or None if it was never used
+    pub fn idle_for(&self) -> Option<Duration> {
+        match self.last_used.load(Ordering::Relaxed) {
+            0 => None,
+            last_used => Some(
+                self.created
+                    .elapsed()
+                    .saturating_sub(Duration::from_millis(last_used - 1)),
+            ),
+        }
+    }
+
+    /// Drop the loaded language, so it is loaded again on its next use
+    /// A failed load is forgotten too, so it is tried again, but only dropping a language returns true
+    pub fn unload(&mut self) -> bool {
+        matches!(self.language.take(), Some(Ok(_)))
+    }
+}
+
+/// Loaded languages are not copied; the copy loads its own the first time it is used
+impl Clone for LazyLanguage {
+    fn clone(&self) -> Self {
+        Self::new(self.loader.clone())
+    }
+}
+
+#[cfg(test)]
+mod test_token {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    #[test]
+    fn test_load_once() {
+        let loads = Arc::new(AtomicUsize::new(0));
+        let counter = loads.clone();
+        let lazy = LazyLanguage::new(LanguageLoader::callback(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Language::new_from_file("examples/fr.lang.json", HashMap::default())
+        }));
+        assert!(!lazy.is_loaded());
+        assert_eq!(lazy.idle_for(), None);
+
+        std::thread::scope(|s| {
+            for _ in 0..4 {
+                s.spawn(|| assert_eq!(lazy.get().unwrap().get("tree"), Some("arbre")));
+            }
+        });
+        assert_eq!(loads.load(Ordering::SeqCst), 1);
+        assert!(lazy.is_loaded());
+        assert!(lazy.idle_for().is_some());
+
+        let mut lazy = lazy;
+        assert!(lazy.unload());
+        assert!(!lazy.unload());
+        assert!(lazy.get().is_ok());
+        assert_eq!(loads.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_loaders() {
+        let embedded = LanguageLoader::Embedded(include_bytes!("../examples/en.lang.json"));
+        assert_eq!(embedded.load().unwrap().short_name(), "en");
+
+        let missing = LazyLanguage::new(LanguageLoader::File("nope.lang.json".to_string()));
+        assert!(missing.get().is_err());
+        assert!(!missing.is_loaded());
+        let mut missing = missing;
+        assert!(!missing.unload());
+        assert!(missing.into_language().is_err());
+        assert_eq!(
+            format!("{:?}", LanguageLoader::File("fr.lang.json".to_string())),
+            "File(\"fr.lang.json\")"
+        );
+    }
+}
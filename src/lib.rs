@@ -23,13 +23,63 @@
 #![doc(html_root_url = "https://docs.rs/embedded-lang/0.9.0")]
 #![warn(missing_docs)]
 
+#[cfg(feature = "async")]
+mod async_loading;
+mod currency_format;
+mod date_format;
+mod key_extraction;
 mod language;
+mod language_format;
+mod language_iter;
+mod language_metadata;
+mod language_scope;
 mod language_set;
+mod lazy_language;
+mod list_format;
+mod locale_data;
 mod macros;
+mod message_format;
+mod missing_keys;
+mod number_format;
+mod plural_rules;
+mod pseudo_locale;
+mod relative_time;
+mod resource_info;
+mod serialization;
+mod string_notes;
+mod string_variants;
+mod telemetry;
+mod translation_status;
+mod unit_format;
 
+#[cfg(feature = "async")]
+pub use async_loading::*;
+pub use currency_format::*;
+pub use date_format::*;
+pub use embedded_lang_macros::declared_resources;
+pub use key_extraction::*;
 pub use language::*;
+pub use language_format::*;
+pub use language_iter::*;
+pub use language_metadata::*;
+pub use language_scope::*;
 pub use language_set::*;
-pub use macros::*;
+pub use lazy_language::{LanguageLoader, LanguageLoaderCallback};
+pub use list_format::*;
+pub use locale_data::*;
+pub use message_format::*;
+pub use missing_keys::*;
+pub use number_format::*;
+pub use plural_rules::*;
+pub use pseudo_locale::*;
+pub use relative_time::*;
+pub use resource_info::*;
+pub use serialization::FORMAT_VERSION;
+pub use string_notes::*;
+pub use string_variants::*;
+pub use telemetry::*;
+pub use translation_status::*;
+pub use unit_format::*;
 
 #[cfg(test)]
 mod test_token {
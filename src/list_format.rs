@@ -0,0 +1,530 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// How the items of a list relate to each other
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum ListType {
+    /// Every item, as in "A, B, and C"
+    And,
+
+    /// Any one item, as in "A, B, or C"
+    Or,
+
+    /// Parts of a single measurement, as in "3 feet, 7 inches"
+    Unit,
+}
+
+impl ListType {
+    /// Find a type by name; `or`, `unit`, or anything else for and
+    ///
+    /// # Arguments
+    /// * `name` - Name of the type
+    pub fn from_name(name: &str) -> Self {
+        match name.trim() {
+            "or" => Self::Or,
+            "unit" => Self::Unit,
+            _ => Self::And,
+        }
+    }
+}
+
+/// How much space a list is written in
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum ListWidth {
+    /// Written out, as in "A, B, and C"
+    Long,
+
+    /// Abbreviated, as in "A, B, & C"
+    Short,
+}
+
+impl ListWidth {
+    /// Find a width by name; `short`, or anything else for long
+    ///
+    /// # Arguments
+    /// * `name` - Name of the width
+    pub fn from_name(name: &str) -> Self {
+        match name.trim() {
+            "short" => Self::Short,
+            _ => Self::Long,
+        }
+    }
+}
+
+/// Patterns joining the items of a list, where `{0}` and `{1}` are the parts being joined
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct ListPatterns {
+    /// Joins the first item to the rest of the list
+    pub start: String,
+
+    /// Joins an item in the middle of the list to the rest of the list
+    pub middle: String,
+
+    /// Joins the last two items of a list of three or more
+    pub end: String,
+
+    /// Joins the items of a list of exactly two
+    pub two: String,
+}
+
+impl ListPatterns {
+    fn parse(patterns: &str) -> Self {
+        let mut parts = patterns.split('|').map(str::to_string);
+        let mut next = || parts.next().unwrap_or_else(|| "{0}, {1}".to_string());
+        Self {
+            start: next(),
+            middle: next(),
+            end: next(),
+            two: next(),
+        }
+    }
+}
+
+/// List patterns for a locale, from the Unicode CLDR, as `start|middle|end|two`
+/// Short lists use the long patterns unless the locale has short ones
+const LIST_DATA: &[(&str, ListType, ListWidth, &str)] = &[
+    (
+        "en",
+        ListType::And,
+        ListWidth::Long,
+        "{0}, {1}|{0}, {1}|{0}, and {1}|{0} and {1}",
+    ),
+    (
+        "en",
+        ListType::And,
+        ListWidth::Short,
+        "{0}, {1}|{0}, {1}|{0}, & {1}|{0} & {1}",
+    ),
+    (
+        "en",
+        ListType::Or,
+        ListWidth::Long,
+        "{0}, {1}|{0}, {1}|{0}, or {1}|{0} or {1}",
+    ),
+    (
+        "en",
+        ListType::Unit,
+        ListWidth::Long,
+        "{0}, {1}|{0}, {1}|{0}, {1}|{0}, {1}",
+    ),
+    (
+        "en-GB",
+        ListType::And,
+        ListWidth::Long,
+        "{0}, {1}|{0}, {1}|{0} and {1}|{0} and {1}",
+    ),
+    (
+        "en-GB",
+        ListType::And,
+        ListWidth::Short,
+        "{0}, {1}|{0}, {1}|{0} and {1}|{0} and {1}",
+    ),
+    (
+        "en-GB",
+        ListType::Or,
+        ListWidth::Long,
+        "{0}, {1}|{0}, {1}|{0} or {1}|{0} or {1}",
+    ),
+    (
+        "fr",
+        ListType::And,
+        ListWidth::Long,
+        "{0}, {1}|{0}, {1}|{0} et {1}|{0} et {1}",
+    ),
+    (
+        "fr",
+        ListType::Or,
+        ListWidth::Long,
+        "{0}, {1}|{0}, {1}|{0} ou {1}|{0} ou {1}",
+    ),
+    (
+        "fr",
+        ListType::Unit,
+        ListWidth::Long,
+        "{0}, {1}|{0}, {1}|{0} et {1}|{0} et {1}",
+    ),
+    (
+        "fr",
+        ListType::Unit,
+        ListWidth::Short,
+        "{0}, {1}|{0}, {1}|{0}, {1}|{0}, {1}",
+    ),
+    (
+        "de",
+        ListType::And,
+        ListWidth::Long,
+        "{0}, {1}|{0}, {1}|{0} und {1}|{0} und {1}",
+    ),
+    (
+        "de",
+        ListType::Or,
+        ListWidth::Long,
+        "{0}, {1}|{0}, {1}|{0} oder {1}|{0} oder {1}",
+    ),
+    (
+        "de",
+        ListType::Unit,
+        ListWidth::Long,
+        "{0}, {1}|{0}, {1}|{0} und {1}|{0} und {1}",
+    ),
+    (
+        "de",
+        ListType::Unit,
+        ListWidth::Short,
+        "{0}, {1}|{0}, {1}|{0} und {1}|{0}, {1}",
+    ),
+    (
+        "es",
+        ListType::And,
+        ListWidth::Long,
+        "{0}, {1}|{0}, {1}|{0} y {1}|{0} y {1}",
+    ),
+    (
+        "es",
+        ListType::Or,
+        ListWidth::Long,
+        "{0}, {1}|{0}, {1}|{0} o {1}|{0} o {1}",
+    ),
+    (
+        "es",
+        ListType::Unit,
+        ListWidth::Long,
+        "{0}, {1}|{0}, {1}|{0} y {1}|{0} y {1}",
+    ),
+    (
+        "es",
+        ListType::Unit,
+        ListWidth::Short,
+        "{0}, {1}|{0}, {1}|{0}, {1}|{0}, {1}",
+    ),
+    (
+        "it",
+        ListType::And,
+        ListWidth::Long,
+        "{0}, {1}|{0}, {1}|{0} e {1}|{0} e {1}",
+    ),
+    (
+        "it",
+        ListType::Or,
+        ListWidth::Long,
+        "{0}, {1}|{0}, {1}|{0} o {1}|{0} o {1}",
+    ),
+    (
+        "it",
+        ListType::Unit,
+        ListWidth::Long,
+        "{0}, {1}|{0}, {1}|{0} e {1}|{0} e {1}",
+    ),
+    (
+        "pt",
+        ListType::And,
+        ListWidth::Long,
+        "{0}, {1}|{0}, {1}|{0} e {1}|{0} e {1}",
+    ),
+    (
+        "pt",
+        ListType::Or,
+        ListWidth::Long,
+        "{0}, {1}|{0}, {1}|{0} ou {1}|{0} ou {1}",
+    ),
+    (
+        "pt",
+        ListType::Unit,
+        ListWidth::Long,
+        "{0}, {1}|{0}, {1}|{0} e {1}|{0} e {1}",
+    ),
+    (
+        "nl",
+        ListType::And,
+        ListWidth::Long,
+        "{0}, {1}|{0}, {1}|{0} en {1}|{0} en {1}",
+    ),
+    (
+        "nl",
+        ListType::Or,
+        ListWidth::Long,
+        "{0}, {1}|{0}, {1}|{0} of {1}|{0} of {1}",
+    ),
+    (
+        "nl",
+        ListType::Unit,
+        ListWidth::Long,
+        "{0}, {1}|{0}, {1}|{0} en {1}|{0} en {1}",
+    ),
+    (
+        "sv",
+        ListType::And,
+        ListWidth::Long,
+        "{0}, {1}|{0}, {1}|{0} och {1}|{0} och {1}",
+    ),
+    (
+        "sv",
+        ListType::Or,
+        ListWidth::Long,
+        "{0}, {1}|{0}, {1}|{0} eller {1}|{0} eller {1}",
+    ),
+    (
+        "sv",
+        ListType::Unit,
+        ListWidth::Long,
+        "{0}, {1}|{0}, {1}|{0} och {1}|{0} och {1}",
+    ),
+    (
+        "pl",
+        ListType::And,
+        ListWidth::Long,
+        "{0}, {1}|{0}, {1}|{0} i {1}|{0} i {1}",
+    ),
+    (
+        "pl",
+        ListType::Or,
+        ListWidth::Long,
+        "{0}, {1}|{0}, {1}|{0} lub {1}|{0} lub {1}",
+    ),
+    (
+        "pl",
+        ListType::Unit,
+        ListWidth::Long,
+        "{0}, {1}|{0}, {1}|{0} i {1}|{0} i {1}",
+    ),
+    (
+        "ru",
+        ListType::And,
+        ListWidth::Long,
+        "{0}, {1}|{0}, {1}|{0} и {1}|{0} и {1}",
+    ),
+    (
+        "ru",
+        ListType::Or,
+        ListWidth::Long,
+        "{0}, {1}|{0}, {1}|{0} или {1}|{0} или {1}",
+    ),
+    (
+        "ru",
+        ListType::Unit,
+        ListWidth::Long,
+        "{0} {1}|{0} {1}|{0} {1}|{0} {1}",
+    ),
+    (
+        "ja",
+        ListType::And,
+        ListWidth::Long,
+        "{0}、{1}|{0}、{1}|{0}、{1}|{0}、{1}",
+    ),
+    (
+        "ja",
+        ListType::Or,
+        ListWidth::Long,
+        "{0}、{1}|{0}、{1}|{0}、または{1}|{0}または{1}",
+    ),
+    (
+        "ja",
+        ListType::Unit,
+        ListWidth::Long,
+        "{0} {1}|{0} {1}|{0} {1}|{0} {1}",
+    ),
+    (
+        "zh",
+        ListType::And,
+        ListWidth::Long,
+        "{0}、{1}|{0}、{1}|{0}和{1}|{0}和{1}",
+    ),
+    (
+        "zh",
+        ListType::Or,
+        ListWidth::Long,
+        "{0}、{1}|{0}、{1}|{0}或{1}|{0}或{1}",
+    ),
+    (
+        "zh",
+        ListType::Unit,
+        ListWidth::Long,
+        "{0}{1}|{0}{1}|{0}{1}|{0}{1}",
+    ),
+    (
+        "ko",
+        ListType::And,
+        ListWidth::Long,
+        "{0}, {1}|{0}, {1}|{0} 및 {1}|{0} 및 {1}",
+    ),
+    (
+        "ko",
+        ListType::Or,
+        ListWidth::Long,
+        "{0}, {1}|{0}, {1}|{0} 또는 {1}|{0} 또는 {1}",
+    ),
+    (
+        "ko",
+        ListType::Unit,
+        ListWidth::Long,
+        "{0} {1}|{0} {1}|{0} {1}|{0} {1}",
+    ),
+];
+
+/// Joins lists of items, such as "Alice, Bob, and Carol", using the conventions of a locale
+#[derive(Clone, Debug, PartialEq)]
+pub struct ListFormatter {
+    patterns: HashMap<(ListType, ListWidth), ListPatterns>,
+}
+
+impl Default for ListFormatter {
+    fn default() -> Self {
+        Self::new("en")
+    }
+}
+
+impl ListFormatter {
+    /// Create a formatter using the built-in conventions of a language
+    /// Languages without built-in patterns use English ones, unless their language file provides some
+    ///
+    /// # Arguments
+    /// * `language` - Language code, such as `fr` or `en-GB`
+    pub fn new(language: &str) -> Self {
+        // Patterns for the primary language first, so the full code can replace them
+        let language = language.replace('_', "-");
+        let primary = language.split('-').next().unwrap_or_default();
+        let primary = if LIST_DATA
+            .iter()
+            .any(|(code, ..)| code.eq_ignore_ascii_case(primary))
+        {
+            primary
+        } else {
+            "en"
+        };
+
+        let mut patterns = HashMap::default();
+        for code in [primary, &language] {
+            patterns.extend(
+                LIST_DATA
+                    .iter()
+                    .filter(|(locale, ..)| locale.eq_ignore_ascii_case(code))
+                    .map(|(_, ty, width, p)| ((*ty, *width), ListPatterns::parse(p))),
+            );
+        }
+        Self { patterns }
+    }
+
+    /// Replace the patterns used for some types of list
+    ///
+    /// # Arguments
+    /// * `overrides` - Patterns by type and width, such as `and` then `long`
+    pub fn with_overrides(
+        mut self,
+        overrides: &HashMap<ListType, HashMap<ListWidth, ListPatterns>>,
+    ) -> Self {
+        for (ty, widths) in overrides {
+            for (width, patterns) in widths {
+                self.patterns.insert((*ty, *width), patterns.clone());
+            }
+        }
+        self
+    }
+
+    /// Join a list of items
+    ///
+    /// # Arguments
+    /// * `items` - Items to join
+    /// * `ty` - How the items relate to each other
+    /// * `width` - How much space to write the list in
+    pub fn format<S: AsRef<str>>(&self, items: &[S], ty: ListType, width: ListWidth) -> String {
+        let patterns = self
+            .patterns
+            .get(&(ty, width))
+            .or_else(|| self.patterns.get(&(ty, ListWidth::Long)))
+            .or_else(|| self.patterns.get(&(ListType::And, ListWidth::Long)));
+        let Some(patterns) = patterns else {
+            return items
+                .iter()
+                .map(AsRef::as_ref)
+                .collect::<Vec<_>>()
+                .join(", ");
+        };
+
+        let join = |pattern: &str, first: &str, rest: &str| {
+            pattern
+                .replace("{0}", "\u{0}")
+                .replace("{1}", rest)
+                .replace('\u{0}', first)
+        };
+        match items {
+            [] => String::new(),
+            [item] => item.as_ref().to_string(),
+            [first, second] => join(&patterns.two, first.as_ref(), second.as_ref()),
+            [first, middle @ .., last] => {
+                let (before_last, middle) = middle.split_last().unwrap_or((first, &[]));
+                let mut output = join(&patterns.end, before_last.as_ref(), last.as_ref());
+                for item in middle.iter().rev() {
+                    output = join(&patterns.middle, item.as_ref(), &output);
+                }
+                join(&patterns.start, first.as_ref(), &output)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_token {
+    use super::*;
+
+    #[test]
+    fn test_format() {
+        let en = ListFormatter::new("en-US");
+        let names = ["Alice", "Bob", "Carol", "Dave"];
+        assert_eq!(en.format(&names[..0], ListType::And, ListWidth::Long), "");
+        assert_eq!(
+            en.format(&names[..1], ListType::And, ListWidth::Long),
+            "Alice"
+        );
+        assert_eq!(
+            en.format(&names[..2], ListType::And, ListWidth::Long),
+            "Alice and Bob"
+        );
+        assert_eq!(
+            en.format(&names[..3], ListType::And, ListWidth::Long),
+            "Alice, Bob, and Carol"
+        );
+        assert_eq!(
+            en.format(&names, ListType::Or, ListWidth::Short),
+            "Alice, Bob, Carol, or Dave"
+        );
+        assert_eq!(
+            en.format(&names[..3], ListType::And, ListWidth::Short),
+            "Alice, Bob, & Carol"
+        );
+        assert_eq!(
+            ListFormatter::new("en-GB").format(&names[..3], ListType::And, ListWidth::Long),
+            "Alice, Bob and Carol"
+        );
+        assert_eq!(
+            ListFormatter::new("de").format(&names, ListType::And, ListWidth::Long),
+            "Alice, Bob, Carol und Dave"
+        );
+        assert_eq!(
+            ListFormatter::new("zh").format(&names[..3], ListType::And, ListWidth::Long),
+            "Alice、Bob和Carol"
+        );
+        assert_eq!(
+            ListFormatter::new("en").format(&["{1}", "{0}"], ListType::And, ListWidth::Long),
+            "{1} and {0}"
+        );
+    }
+
+    #[test]
+    fn test_overrides() {
+        let overrides = serde_json::from_str(
+            r#"{"and": {"long": {"start": "{0}, {1}", "middle": "{0}, {1}", "end": "{0} ja {1}", "two": "{0} ja {1}"}}}"#,
+        )
+        .unwrap();
+        let fi = ListFormatter::new("fi").with_overrides(&overrides);
+        assert_eq!(
+            fi.format(&["a", "b", "c"], ListType::And, ListWidth::Long),
+            "a, b ja c"
+        );
+        assert_eq!(
+            fi.format(&["a", "b", "c"], ListType::Or, ListWidth::Long),
+            "a, b, or c"
+        );
+    }
+}
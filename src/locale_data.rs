@@ -0,0 +1,67 @@
+use serde::de::{self, Deserializer};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::{
+    DateOverrides, ListPatterns, ListType, ListWidth, RelativeUnit, RelativeUnitPatterns, Unit,
+    UnitPatterns, UnitWidth,
+};
+
+/// Formatting conventions customized by a language file, stored in its `locale` section
+/// Anything left out uses the built-in data for the language's code
+#[derive(Serialize, Deserialize, Clone, Debug, Default, Eq, PartialEq)]
+pub struct LocaleData {
+    /// Month and day names, and date and time patterns
+    #[serde(default, skip_serializing_if = "DateOverrides::is_empty")]
+    pub dates: DateOverrides,
+
+    /// Where the currency symbol goes, where `¤` is the symbol and `{0}` the amount
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency_pattern: Option<String>,
+
+    /// Currency symbols, by ISO 4217 code
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub currency_symbols: HashMap<String, String>,
+
+    /// Unit patterns by width, unit name and plural category, such as `long`, `kilometer`, `one`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub units: HashMap<UnitWidth, HashMap<String, UnitPatterns>>,
+
+    /// Relative time phrases by unit, such as `day`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub relative: HashMap<RelativeUnit, RelativeUnitPatterns>,
+
+    /// List patterns by type and width, such as `and` then `long`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub lists: HashMap<ListType, HashMap<ListWidth, ListPatterns>>,
+}
+
+impl LocaleData {
+    /// Deserialize locale data, rejecting data that fails [`LocaleData::validate`]
+    pub(crate) fn deserialize_valid<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let data = Self::deserialize(deserializer)?;
+        data.validate().map_err(de::Error::custom)?;
+        Ok(data)
+    }
+
+    /// Return true if nothing is customized
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Check that the customized data is usable
+    pub fn validate(&self) -> Result<(), String> {
+        self.dates.validate()?;
+        match self
+            .units
+            .values()
+            .flat_map(HashMap::keys)
+            .find(|name| Unit::from_name(name).is_none())
+        {
+            Some(name) => Err(format!("{} is not a known unit", name)),
+            None => Ok(()),
+        }
+    }
+}
@@ -1,15 +1,17 @@
 /// Get a language as an embedded resource
+/// Resources declared in the file's `resources` section are embedded along with it
 ///
 /// # Arguments
 /// * `filename` - Path to the file to embed
 #[macro_export]
 macro_rules! embedded_language {
-    ($filename:literal, resources = [ $($rname:literal: $rfilename:expr),+ ]) => {
-        embedded_lang::Language::new_from_string(include_str!($filename), std::collections::HashMap::from([$(($rname.to_string(), include_bytes!($rfilename).to_vec())),+])).unwrap()
-
-    };
+    ($filename:literal, resources = [ $($rname:literal: $rfilename:expr),+ ]) => {{
+        let mut resources = embedded_lang::declared_resources!($filename);
+        resources.extend([$(($rname.to_string(), include_bytes!($rfilename).to_vec())),+]);
+        embedded_lang::Language::new_from_string(include_str!($filename), resources).unwrap()
+    }};
     ($filename:literal) => {
-        embedded_lang::Language::new_from_string(include_str!($filename), std::collections::HashMap::from([])).unwrap()
+        embedded_lang::Language::new_from_string(include_str!($filename), embedded_lang::declared_resources!($filename)).unwrap()
 
     };
 }
@@ -25,10 +27,22 @@ macro_rules! get_string {
     };
 }
 
+/// Get a language string, applying the set's missing key policy if it cannot be found
+///
+/// # Arguments
+/// * `set` - Language set or scope to search
+/// * `name` - String to find
+#[macro_export]
+macro_rules! lookup_string {
+    ($set:expr, $name:expr) => {
+        $set.lookup($name)
+    };
+}
+
 #[cfg(test)]
 mod test_token {
     use crate as embedded_lang;
-    use crate::LanguageSet;
+    use crate::{LanguageSet, MissingKeyPolicy};
 
     #[test]
     fn test_embedded_language() {
@@ -42,6 +56,13 @@ mod test_token {
             "license_file":"../LICENSE"
         ]);
         assert_eq!(lang.get("hello_msg"), Some("hello world!"));
+        assert!(lang.binary_resource("license_file").is_some());
+
+        let lang = embedded_language!("../examples/fr.lang.json");
+        assert_eq!(
+            lang.utf8_resource("welcome"),
+            Some("Bienvenue dans embedded-lang!\n")
+        );
     }
 
     #[test]
@@ -71,4 +92,18 @@ mod test_token {
         assert_eq!(get_string!(set, "foobar"), "");
         assert_eq!(get_string!(set, "mustard"), "mustard");
     }
+
+    #[test]
+    fn test_lookup_string() {
+        let mut set = LanguageSet::new("fr", &[embedded_language!("../examples/fr.lang.json")]);
+        set.set_missing_key_policy(MissingKeyPolicy::Key);
+
+        assert_eq!(lookup_string!(set, "tree"), "arbre");
+        assert_eq!(lookup_string!(set, "foobar"), "foobar");
+        assert_eq!(get_string!(set, "foobar"), "");
+        assert_eq!(
+            lookup_string!(set.scope("category"), "nope"),
+            "category\\nope"
+        );
+    }
 }
@@ -0,0 +1,619 @@
+use std::time::Duration;
+
+use crate::string_variants::OTHER_SELECTOR;
+use crate::{
+    bidi_isolate, CurrencyDisplay, CurrencyFormatter, DateFormatter, DateLength, DateSymbols,
+    DateTime, Language, ListFormatter, ListType, ListWidth, NumberFormatter, NumberStyle,
+    RelativeNumeric, RelativeTimeFormatter, RelativeUnit, TextDirection, Unit, UnitFormatter,
+    UnitWidth,
+};
+
+/// A value substituted into a placeholder of a string
+#[derive(Clone, Debug, PartialEq)]
+pub enum FormatArg {
+    /// Text, inserted as-is
+    Str(String),
+
+    /// A number, written using the conventions of the language
+    Number(f64),
+
+    /// A date and time, written using the conventions of the language
+    Date(DateTime),
+
+    /// A list of items, joined using the conventions of the language
+    List(Vec<String>),
+}
+
+impl From<&str> for FormatArg {
+    fn from(value: &str) -> Self {
+        Self::Str(value.to_string())
+    }
+}
+
+impl From<String> for FormatArg {
+    fn from(value: String) -> Self {
+        Self::Str(value)
+    }
+}
+
+impl From<DateTime> for FormatArg {
+    fn from(value: DateTime) -> Self {
+        Self::Date(value)
+    }
+}
+
+impl From<Vec<String>> for FormatArg {
+    fn from(value: Vec<String>) -> Self {
+        Self::List(value)
+    }
+}
+
+impl From<&[&str]> for FormatArg {
+    fn from(value: &[&str]) -> Self {
+        Self::List(value.iter().map(|s| s.to_string()).collect())
+    }
+}
+
+macro_rules! number_format_arg {
+    ($($t:ty),+) => {
+        $(
+            impl From<$t> for FormatArg {
+                fn from(value: $t) -> Self {
+                    Self::Number(value as f64)
+                }
+            }
+        )+
+    };
+}
+number_format_arg!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64);
+
+/// A placeholder within a string, such as `{n, number, percent}`
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct Placeholder<'a> {
+    /// Name of the argument to substitute
+    pub name: &'a str,
+
+    /// How to write the argument, such as `number`
+    pub kind: &'a str,
+
+    /// Options for the kind of placeholder, such as `percent`
+    pub style: &'a str,
+}
+
+impl<'a> Placeholder<'a> {
+    fn parse(body: &'a str) -> Self {
+        let mut parts = body.splitn(3, ',').map(str::trim);
+        Self {
+            name: parts.next().unwrap_or_default(),
+            kind: parts.next().unwrap_or_default(),
+            style: parts.next().unwrap_or_default(),
+        }
+    }
+}
+
+/// Part of a string, either literal text or a placeholder
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Part<'a> {
+    Text(String),
+    Placeholder(&'a str),
+}
+
+/// Split a string into literal text and placeholders
+/// `{{` and `}}` are written as literal braces; a brace without a match is kept as text
+pub(crate) fn parse(pattern: &str) -> Vec<Part<'_>> {
+    let mut parts = vec![];
+    let mut text = String::new();
+    let mut chars = pattern.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '{' if chars.peek().map(|(_, c)| *c) == Some('{') => {
+                chars.next();
+                text.push('{');
+            }
+            '}' if chars.peek().map(|(_, c)| *c) == Some('}') => {
+                chars.next();
+                text.push('}');
+            }
+            '{' => {
+                let mut depth = 0;
+                let end = pattern[i..].char_indices().find_map(|(j, c)| {
+                    match c {
+                        '{' => depth += 1,
+                        '}' => depth -= 1,
+                        _ => {}
+                    }
+                    (depth == 0).then_some(i + j)
+                });
+
+                match end {
+                    Some(end) => {
+                        if !text.is_empty() {
+                            parts.push(Part::Text(std::mem::take(&mut text)));
+                        }
+                        parts.push(Part::Placeholder(&pattern[i + 1..end]));
+                        while chars.peek().is_some_and(|(j, _)| *j <= end) {
+                            chars.next();
+                        }
+                    }
+                    None => text.push(c),
+                }
+            }
+            _ => text.push(c),
+        }
+    }
+
+    if !text.is_empty() {
+        parts.push(Part::Text(text));
+    }
+    parts
+}
+
+/// Substitutes arguments into the placeholders of strings, using the conventions of a language
+///
+/// Placeholders are written as `{name}`, or `{name, kind}` and `{name, kind, style}` to choose
+/// how the argument is written:
+/// - `{n, number}`, `{n, number, integer}`, `{n, number, percent}` and `{n, number, scientific}`
+/// - `{d, date}`, `{d, time}` and `{d, datetime}`, followed by `short`, `medium`, `long`, `full`
+///   or a pattern such as `d MMMM`
+/// - `{n, currency, EUR}`, optionally followed by `narrow` or `code`, as in `{n, currency, EUR code}`
+/// - `{n, unit, kilometer}`, optionally followed by `long` or `narrow`
+/// - `{n, bytes}` and `{n, duration}` for a number of bytes or seconds, optionally followed by `long` or `narrow`
+/// - `{n, relative, day}` for a distance in time in a unit, or `{d, relative}` for the distance from now to a date;
+///   followed by `auto` to allow names such as "yesterday", as in `{n, relative, day auto}`
+/// - `{items, list}`, optionally followed by `and`, `or` or `unit` and then `short`, as in `{items, list, or short}`
+/// - `{g, select, male {He} female {She} other {They}}` to choose text by the argument, or `other` if none match;
+///   placeholders within the chosen text are substituted too
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MessageFormatter {
+    language: String,
+    numbers: NumberFormatter,
+    dates: DateFormatter,
+    currencies: CurrencyFormatter,
+    units: UnitFormatter,
+    relative: RelativeTimeFormatter,
+    lists: ListFormatter,
+    isolate: bool,
+}
+
+impl MessageFormatter {
+    /// Create a formatter using the built-in conventions of a language code
+    ///
+    /// # Arguments
+    /// * `language` - Language code, such as `fr` or `de-CH`
+    pub fn new(language: &str) -> Self {
+        Self {
+            language: language.to_string(),
+            numbers: NumberFormatter::new(language),
+            dates: DateFormatter::new(language),
+            currencies: CurrencyFormatter::new(language),
+            units: UnitFormatter::new(language),
+            relative: RelativeTimeFormatter::new(language),
+            lists: ListFormatter::new(language),
+            isolate: false,
+        }
+    }
+
+    /// Create a formatter using the conventions of a language
+    /// Conventions customized in the language's `locale` section replace the built-in ones,
+    /// and arguments are isolated if the language is written right to left
+    ///
+    /// # Arguments
+    /// * `language` - Language to use
+    pub fn for_language(language: &Language) -> Self {
+        let code = language.short_name();
+        let locale = language.locale();
+        Self {
+            dates: DateFormatter::with_symbols(
+                DateSymbols::for_language(code).with_overrides(&locale.dates),
+            ),
+            currencies: CurrencyFormatter::new(code)
+                .with_overrides(locale.currency_pattern.as_deref(), &locale.currency_symbols),
+            units: UnitFormatter::new(code)
+                .with_plural_rules(language.plural_rules())
+                .with_overrides(&locale.units),
+            relative: RelativeTimeFormatter::new(code)
+                .with_plural_rules(language.plural_rules())
+                .with_overrides(&locale.relative),
+            lists: ListFormatter::new(code).with_overrides(&locale.lists),
+            isolate: language.direction() == TextDirection::Rtl,
+            ..Self::new(code)
+        }
+    }
+
+    /// Choose whether arguments are wrapped in bidi isolation marks, as by [`bidi_isolate`]
+    /// This keeps left-to-right arguments readable inside right-to-left strings, and the reverse
+    ///
+    /// # Arguments
+    /// * `isolate` - True to isolate arguments
+    pub fn with_isolation(mut self, isolate: bool) -> Self {
+        self.isolate = isolate;
+        self
+    }
+
+    /// Return true if arguments are wrapped in bidi isolation marks
+    pub fn isolates(&self) -> bool {
+        self.isolate
+    }
+
+    /// Return the code of the language used by this formatter
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    /// Return the number formatter used by this formatter
+    pub fn numbers(&self) -> &NumberFormatter {
+        &self.numbers
+    }
+
+    /// Return the date formatter used by this formatter
+    pub fn dates(&self) -> &DateFormatter {
+        &self.dates
+    }
+
+    /// Return the currency formatter used by this formatter
+    pub fn currencies(&self) -> &CurrencyFormatter {
+        &self.currencies
+    }
+
+    /// Return the unit formatter used by this formatter
+    pub fn units(&self) -> &UnitFormatter {
+        &self.units
+    }
+
+    /// Return the relative time formatter used by this formatter
+    pub fn relative(&self) -> &RelativeTimeFormatter {
+        &self.relative
+    }
+
+    /// Return the list formatter used by this formatter
+    pub fn lists(&self) -> &ListFormatter {
+        &self.lists
+    }
+
+    /// Substitute arguments into the placeholders of a string
+    /// Placeholders without a matching argument are left as-is
+    /// Text chosen by `select` placeholders is not isolated, but the arguments within it are
+    ///
+    /// # Arguments
+    /// * `pattern` - String containing placeholders
+    /// * `args` - Names and values of the arguments
+    pub fn format(&self, pattern: &str, args: &[(&str, FormatArg)]) -> String {
+        parse(pattern)
+            .into_iter()
+            .map(|part| match part {
+                Part::Text(text) => text,
+                Part::Placeholder(body) => {
+                    let placeholder = Placeholder::parse(body);
+                    args.iter()
+                        .find(|(name, _)| *name == placeholder.name)
+                        .map(|(_, arg)| match self.format_arg(arg, &placeholder, args) {
+                            text if self.isolate && placeholder.kind != "select" => {
+                                bidi_isolate(&text)
+                            }
+                            text => text,
+                        })
+                        .unwrap_or_else(|| format!("{{{}}}", body))
+                }
+            })
+            .collect()
+    }
+
+    /// Write a single argument as described by its placeholder
+    /// The other arguments are substituted into the text chosen by `select` placeholders
+    fn format_arg(
+        &self,
+        arg: &FormatArg,
+        placeholder: &Placeholder,
+        args: &[(&str, FormatArg)],
+    ) -> String {
+        match (placeholder.kind, arg) {
+            ("number", FormatArg::Number(n)) => self
+                .numbers
+                .format(*n, NumberStyle::from_name(placeholder.style)),
+            ("currency", FormatArg::Number(n)) => {
+                let (code, display) = split_style(placeholder.style);
+                self.currencies
+                    .format(*n, code, CurrencyDisplay::from_name(display))
+            }
+            ("unit", FormatArg::Number(n)) => {
+                let (name, width) = split_style(placeholder.style);
+                match Unit::from_name(name) {
+                    Some(unit) => self.units.format(*n, unit, UnitWidth::from_name(width)),
+                    None => self.numbers.format(*n, NumberStyle::Decimal),
+                }
+            }
+            ("bytes", FormatArg::Number(n)) => self
+                .units
+                .format_bytes(n.max(0.0) as u64, UnitWidth::from_name(placeholder.style)),
+            ("duration", FormatArg::Number(n)) => match Duration::try_from_secs_f64(n.max(0.0)) {
+                Ok(duration) => self
+                    .units
+                    .format_duration(duration, UnitWidth::from_name(placeholder.style)),
+                Err(_) => self.numbers.format(*n, NumberStyle::Decimal),
+            },
+            ("relative", FormatArg::Number(n)) => {
+                let (name, numeric) = split_style(placeholder.style);
+                let numeric = relative_numeric(numeric);
+                match RelativeUnit::from_name(name) {
+                    Some(unit) => self.relative.format(*n, unit, numeric),
+                    None => self
+                        .relative
+                        .format_seconds(*n as i64, relative_numeric(name)),
+                }
+            }
+            ("relative", FormatArg::Date(d)) => {
+                self.relative
+                    .format_date(d, &DateTime::now(), relative_numeric(placeholder.style))
+            }
+            (_, FormatArg::Number(n)) => self.numbers.format(*n, NumberStyle::Decimal),
+            (kind @ ("date" | "time" | "datetime"), FormatArg::Date(d)) => {
+                match DateLength::from_name(placeholder.style) {
+                    Some(length) if kind == "date" => self.dates.format_date(d, length),
+                    Some(length) if kind == "time" => self.dates.format_time(d, length),
+                    Some(length) => self.dates.format_datetime(d, length),
+                    None if !placeholder.style.is_empty() => {
+                        self.dates.format_pattern(d, placeholder.style)
+                    }
+                    None if kind == "date" => self.dates.format_date(d, DateLength::Medium),
+                    None if kind == "time" => self.dates.format_time(d, DateLength::Medium),
+                    None => self.dates.format_datetime(d, DateLength::Medium),
+                }
+            }
+            (_, FormatArg::List(items)) => {
+                let (ty, width) = split_style(placeholder.style);
+                self.lists
+                    .format(items, ListType::from_name(ty), ListWidth::from_name(width))
+            }
+            (_, FormatArg::Date(d)) => self.dates.format_datetime(d, DateLength::Medium),
+            ("select", FormatArg::Str(s)) => {
+                let options = select_options(placeholder.style);
+                [s.as_str(), OTHER_SELECTOR]
+                    .into_iter()
+                    .find_map(|selector| options.iter().find(|(key, _)| *key == selector))
+                    .map(|(_, text)| self.format(text, args))
+                    .unwrap_or_default()
+            }
+            (_, FormatArg::Str(s)) => s.clone(),
+        }
+    }
+}
+
+/// Split a placeholder style into its first word and the rest, as in `EUR narrow`
+fn split_style(style: &str) -> (&str, &str) {
+    style.split_once(' ').unwrap_or((style, ""))
+}
+
+fn relative_numeric(style: &str) -> RelativeNumeric {
+    match style.trim() {
+        "auto" => RelativeNumeric::Auto,
+        _ => RelativeNumeric::Always,
+    }
+}
+
+/// Split the options of a `select` placeholder, such as `male {He} other {They}`, into keys and text
+/// Text may contain placeholders of its own; an option without a matching brace ends the list
+fn select_options(style: &str) -> Vec<(&str, &str)> {
+    let mut options = vec![];
+    let mut rest = style;
+    while let Some(open) = rest.find('{') {
+        let mut depth = 0;
+        let close = rest[open..].char_indices().find_map(|(i, c)| {
+            match c {
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                _ => {}
+            }
+            (depth == 0).then_some(open + i)
+        });
+
+        match close {
+            Some(close) => {
+                options.push((rest[..open].trim(), &rest[open + 1..close]));
+                rest = &rest[close + 1..];
+            }
+            None => break,
+        }
+    }
+    options
+}
+
+#[cfg(test)]
+mod test_token {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            parse("a {{b}} {c} {d, number} {"),
+            vec![
+                Part::Text("a {b} ".to_string()),
+                Part::Placeholder("c"),
+                Part::Text(" ".to_string()),
+                Part::Placeholder("d, number"),
+                Part::Text(" {".to_string()),
+            ]
+        );
+        assert_eq!(
+            Placeholder::parse(" n , number , percent "),
+            Placeholder {
+                name: "n",
+                kind: "number",
+                style: "percent"
+            }
+        );
+    }
+
+    #[test]
+    fn test_format() {
+        let en = MessageFormatter::new("en");
+        assert_eq!(
+            en.format(
+                "{name} has {n} points ({p, number, percent})",
+                &[
+                    ("name", "Bob".into()),
+                    ("n", 1234.5.into()),
+                    ("p", 0.5.into())
+                ]
+            ),
+            "Bob has 1,234.5 points (50%)"
+        );
+        assert_eq!(en.format("{missing} {{x}}", &[]), "{missing} {x}");
+
+        let de = MessageFormatter::new("de");
+        assert_eq!(
+            de.format("{n, number, integer}", &[("n", 1234.6.into())]),
+            "1.235"
+        );
+    }
+
+    #[test]
+    fn test_format_dates() {
+        let date = DateTime::new(2025, 3, 14)
+            .unwrap()
+            .with_time(9, 5, 0)
+            .unwrap();
+        let args = [("d", date.into())];
+
+        let en = MessageFormatter::new("en");
+        assert_eq!(en.format("{d, date}", &args), "Mar 14, 2025");
+        assert_eq!(en.format("{d, time, short}", &args), "9:05 AM");
+        assert_eq!(en.format("{d, date, d MMMM}", &args), "14 March");
+        assert_eq!(en.format("{d}", &args), "Mar 14, 2025, 9:05:00 AM");
+
+        let mut language = Language::new(
+            "English".to_string(),
+            "en".to_string(),
+            Default::default(),
+            Default::default(),
+        );
+        language
+            .locale_mut()
+            .dates
+            .date_patterns
+            .insert(DateLength::Medium, "y-MM-dd".to_string());
+        let custom = MessageFormatter::for_language(&language);
+        assert_eq!(custom.format("{d, date}", &args), "2025-03-14");
+        assert_eq!(custom.format("{d, date, long}", &args), "March 14, 2025");
+    }
+
+    #[test]
+    fn test_format_units() {
+        let en = MessageFormatter::new("en");
+        assert_eq!(
+            en.format(
+                "{total, currency, USD} for {size, bytes} ({d, unit, kilometer long})",
+                &[
+                    ("total", 9.5.into()),
+                    ("size", 1_500_000.into()),
+                    ("d", 1.into())
+                ]
+            ),
+            "$9.50 for 1.5 MB (1 kilometer)"
+        );
+        assert_eq!(
+            en.format("{t, duration, long}", &[("t", 90.into())]),
+            "1.5 minutes"
+        );
+        assert_eq!(
+            en.format("{t, duration}", &[("t", f64::INFINITY.into())]),
+            en.format("{t}", &[("t", f64::INFINITY.into())])
+        );
+        assert_eq!(
+            en.format("{t, duration}", &[("t", 1e300.into())]),
+            en.format("{t}", &[("t", 1e300.into())])
+        );
+
+        let ru = MessageFormatter::new("ru");
+        assert_eq!(
+            ru.format(
+                "{n, unit, day long}, {p, currency, EUR code}",
+                &[("n", 22.into()), ("p", 3.into())]
+            ),
+            "22 дня, 3,00\u{A0}EUR"
+        );
+
+        let mut language = Language::new(
+            "Français".to_string(),
+            "fr".to_string(),
+            Default::default(),
+            Default::default(),
+        );
+        let args = [("d", 1.5.into())];
+        assert_eq!(
+            MessageFormatter::for_language(&language).format("{d, unit, kilometer long}", &args),
+            "1,5 kilomètre"
+        );
+        language.metadata_mut().plural_rules = Some("en".to_string());
+        assert_eq!(
+            MessageFormatter::for_language(&language).format("{d, unit, kilometer long}", &args),
+            "1,5 kilomètres"
+        );
+    }
+
+    #[test]
+    fn test_format_relative() {
+        let en = MessageFormatter::new("en");
+        assert_eq!(
+            en.format(
+                "Edited {t, relative, day auto}, saved {s, relative}",
+                &[("t", (-1).into()), ("s", (-180).into())]
+            ),
+            "Edited yesterday, saved 3 minutes ago"
+        );
+    }
+
+    #[test]
+    fn test_format_list() {
+        let items: &[&str] = &["red", "green", "blue"];
+        assert_eq!(
+            MessageFormatter::new("en").format("Pick {c, list, or}", &[("c", items.into())]),
+            "Pick red, green, or blue"
+        );
+        assert_eq!(
+            MessageFormatter::new("es").format("{c}", &[("c", items.into())]),
+            "red, green y blue"
+        );
+    }
+
+    #[test]
+    fn test_format_select() {
+        let en = MessageFormatter::new("en");
+        let pattern = "{g, select, male {He has {n} points} female {She has {n} points} other {They have {n} points}}";
+        assert_eq!(
+            en.format(pattern, &[("g", "female".into()), ("n", 1200.into())]),
+            "She has 1,200 points"
+        );
+        assert_eq!(
+            en.format(pattern, &[("g", "robot".into()), ("n", 3.into())]),
+            "They have 3 points"
+        );
+        assert_eq!(
+            en.format("{g, select, male {He}}", &[("g", "x".into())]),
+            ""
+        );
+    }
+
+    #[test]
+    fn test_isolation() {
+        let ar = Language::new_from_string(
+            r#"{"name": "العربية", "short_name": "ar", "strings": {}}"#,
+            Default::default(),
+        )
+        .unwrap();
+        let formatter = MessageFormatter::for_language(&ar);
+        assert!(formatter.isolates());
+        assert!(!formatter.clone().with_isolation(false).isolates());
+        assert_eq!(
+            formatter.format("مرحبا {name}", &[("name", "Bob".into())]),
+            "مرحبا \u{2068}Bob\u{2069}"
+        );
+        assert_eq!(
+            formatter.format(
+                "{g, select, other {{name}}}",
+                &[("g", "x".into()), ("name", "Bob".into())]
+            ),
+            "\u{2068}Bob\u{2069}"
+        );
+        assert!(!MessageFormatter::new("ar").isolates());
+    }
+}
@@ -0,0 +1,163 @@
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// Function called with the language code and key of a missing string
+/// Returns the text to use in its place
+pub type MissingKeyCallback = Arc<dyn Fn(&str, &str) -> String + Send + Sync>;
+
+/// What a language set returns when a string cannot be found
+#[derive(Clone, Default)]
+pub enum MissingKeyPolicy {
+    /// Return an empty string
+    #[default]
+    Empty,
+
+    /// Return the key itself
+    Key,
+
+    /// Return the key wrapped in a visible marker, such as `⟦key⟧`
+    Marker,
+
+    /// Panic in debug builds, and return an empty string in release builds
+    Panic,
+
+    /// Call a function to produce the text
+    Callback(MissingKeyCallback),
+}
+
+impl MissingKeyPolicy {
+    /// Create a policy calling the given function for missing strings
+    ///
+    /// # Arguments
+    /// * `callback` - Function called with the language code and key of the missing string
+    pub fn callback<F: Fn(&str, &str) -> String + Send + Sync + 'static>(callback: F) -> Self {
+        Self::Callback(Arc::new(callback))
+    }
+
+    /// Produce the text to use for a missing string
+    ///
+    /// # Arguments
+    /// * `language` - Language code the string was requested in
+    /// * `name` - Key of the missing string
+    pub fn resolve<'a>(&self, language: &str, name: &'a str) -> Cow<'a, str> {
+        match self {
+            Self::Empty => Cow::Borrowed(""),
+            Self::Key => Cow::Borrowed(name),
+            Self::Marker => Cow::Owned(format!("⟦{}⟧", name)),
+            Self::Panic => {
+                if cfg!(debug_assertions) {
+                    panic!("Missing string '{}' in language '{}'", name, language);
+                }
+                Cow::Borrowed("")
+            }
+            Self::Callback(callback) => Cow::Owned(callback(language, name)),
+        }
+    }
+}
+
+impl fmt::Debug for MissingKeyPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "Empty"),
+            Self::Key => write!(f, "Key"),
+            Self::Marker => write!(f, "Marker"),
+            Self::Panic => write!(f, "Panic"),
+            Self::Callback(_) => write!(f, "Callback"),
+        }
+    }
+}
+
+/// A string that could not be found
+#[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct MissingKey {
+    /// Language code the string was requested in
+    pub language: String,
+
+    /// Key of the missing string
+    pub key: String,
+}
+
+/// Records every missing string looked up through a language set
+/// Clones share the same record, so a clone can be kept to read the results later
+#[derive(Clone, Debug, Default)]
+pub struct MissingKeyCollector {
+    missed: Arc<Mutex<HashSet<MissingKey>>>,
+}
+
+impl MissingKeyCollector {
+    /// Create a new, empty collector
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a missing string
+    ///
+    /// # Arguments
+    /// * `language` - Language code the string was requested in
+    /// * `name` - Key of the missing string
+    pub fn record(&self, language: &str, name: &str) {
+        if let Ok(mut missed) = self.missed.lock() {
+            missed.insert(MissingKey {
+                language: language.to_string(),
+                key: name.to_string(),
+            });
+        }
+    }
+
+    /// Return every missing string recorded so far, sorted by language and key
+    pub fn missed(&self) -> Vec<MissingKey> {
+        let mut missed: Vec<MissingKey> = self
+            .missed
+            .lock()
+            .map(|m| m.iter().cloned().collect())
+            .unwrap_or_default();
+        missed.sort();
+        missed
+    }
+
+    /// Forget every missing string recorded so far
+    pub fn clear(&self) {
+        if let Ok(mut missed) = self.missed.lock() {
+            missed.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_token {
+    use super::*;
+
+    #[test]
+    fn test_resolve() {
+        assert_eq!(MissingKeyPolicy::Empty.resolve("fr", "nope"), "");
+        assert_eq!(MissingKeyPolicy::Key.resolve("fr", "nope"), "nope");
+        assert_eq!(MissingKeyPolicy::Marker.resolve("fr", "nope"), "⟦nope⟧");
+        assert_eq!(
+            MissingKeyPolicy::callback(|l, k| format!("{}:{}", l, k)).resolve("fr", "nope"),
+            "fr:nope"
+        );
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn test_resolve_panic() {
+        MissingKeyPolicy::Panic.resolve("fr", "nope");
+    }
+
+    #[test]
+    fn test_collector() {
+        let collector = MissingKeyCollector::new();
+        collector.clone().record("fr", "b");
+        collector.record("fr", "a");
+        collector.record("fr", "b");
+
+        let missed: Vec<String> = collector.missed().into_iter().map(|m| m.key).collect();
+        assert_eq!(missed, vec!["a", "b"]);
+
+        collector.clear();
+        assert!(collector.missed().is_empty());
+    }
+}
@@ -0,0 +1,473 @@
+use serde::{Deserialize, Serialize};
+
+const LATIN_DIGITS: &str = "0123456789";
+const ARABIC_DIGITS: &str = "٠١٢٣٤٥٦٧٨٩";
+const PERSIAN_DIGITS: &str = "۰۱۲۳۴۵۶۷۸۹";
+const DEVANAGARI_DIGITS: &str = "०१२३४५६७८९";
+const BENGALI_DIGITS: &str = "০১২৩৪৫৬৭৮৯";
+
+/// Built-in number data for a locale
+struct NumberData {
+    code: &'static str,
+    decimal: &'static str,
+    group: &'static str,
+    percent: &'static str,
+    grouping: (usize, usize),
+    minimum_grouping_digits: usize,
+    digits: &'static str,
+    minus: &'static str,
+}
+
+impl NumberData {
+    const fn new(code: &'static str, decimal: &'static str, group: &'static str) -> Self {
+        Self {
+            code,
+            decimal,
+            group,
+            percent: "{0}%",
+            grouping: (3, 3),
+            minimum_grouping_digits: 1,
+            digits: LATIN_DIGITS,
+            minus: "-",
+        }
+    }
+
+    const fn percent(mut self, percent: &'static str) -> Self {
+        self.percent = percent;
+        self
+    }
+
+    const fn indian(mut self) -> Self {
+        self.grouping = (3, 2);
+        self
+    }
+
+    const fn minimum_grouping_digits(mut self, digits: usize) -> Self {
+        self.minimum_grouping_digits = digits;
+        self
+    }
+
+    const fn digits(mut self, digits: &'static str) -> Self {
+        self.digits = digits;
+        self
+    }
+
+    const fn minus(mut self, minus: &'static str) -> Self {
+        self.minus = minus;
+        self
+    }
+}
+
+/// Number data for common locales, from the Unicode CLDR
+const NUMBER_DATA: &[NumberData] = &[
+    NumberData::new("en", ".", ","),
+    NumberData::new("en-IN", ".", ",").indian(),
+    NumberData::new("fr", ",", "\u{202F}").percent("{0}\u{202F}%"),
+    NumberData::new("fr-CH", ",", "\u{202F}").percent("{0}%"),
+    NumberData::new("de", ",", ".").percent("{0}\u{A0}%"),
+    NumberData::new("de-AT", ",", "\u{A0}").percent("{0}\u{A0}%"),
+    NumberData::new("de-CH", ".", "’").percent("{0}%"),
+    NumberData::new("es", ",", ".")
+        .percent("{0}\u{A0}%")
+        .minimum_grouping_digits(2),
+    NumberData::new("es-MX", ".", ",").percent("{0}\u{A0}%"),
+    NumberData::new("es-US", ".", ",").percent("{0}\u{A0}%"),
+    NumberData::new("it", ",", "."),
+    NumberData::new("pt", ",", "."),
+    NumberData::new("pt-PT", ",", "\u{A0}").minimum_grouping_digits(2),
+    NumberData::new("nl", ",", "."),
+    NumberData::new("sv", ",", "\u{A0}")
+        .percent("{0}\u{A0}%")
+        .minus("\u{2212}"),
+    NumberData::new("nb", ",", "\u{A0}")
+        .percent("{0}\u{A0}%")
+        .minus("\u{2212}"),
+    NumberData::new("no", ",", "\u{A0}")
+        .percent("{0}\u{A0}%")
+        .minus("\u{2212}"),
+    NumberData::new("da", ",", ".").percent("{0}\u{A0}%"),
+    NumberData::new("fi", ",", "\u{A0}")
+        .percent("{0}\u{A0}%")
+        .minus("\u{2212}"),
+    NumberData::new("pl", ",", "\u{A0}").minimum_grouping_digits(2),
+    NumberData::new("ru", ",", "\u{A0}").percent("{0}\u{A0}%"),
+    NumberData::new("uk", ",", "\u{A0}"),
+    NumberData::new("cs", ",", "\u{A0}").percent("{0}\u{A0}%"),
+    NumberData::new("hu", ",", "\u{A0}"),
+    NumberData::new("ro", ",", ".").percent("{0}\u{A0}%"),
+    NumberData::new("el", ",", "."),
+    NumberData::new("tr", ",", ".").percent("%{0}"),
+    NumberData::new("he", ".", ",").minus("\u{200E}-"),
+    NumberData::new("ar", "٫", "٬")
+        .percent("{0}٪\u{61C}")
+        .digits(ARABIC_DIGITS)
+        .minus("\u{61C}-"),
+    NumberData::new("fa", "٫", "٬")
+        .percent("{0}٪")
+        .digits(PERSIAN_DIGITS)
+        .minus("\u{200E}\u{2212}"),
+    NumberData::new("hi", ".", ",").indian(),
+    NumberData::new("mr", ".", ",")
+        .indian()
+        .digits(DEVANAGARI_DIGITS),
+    NumberData::new("bn", ".", ",")
+        .indian()
+        .digits(BENGALI_DIGITS),
+    NumberData::new("th", ".", ","),
+    NumberData::new("id", ",", "."),
+    NumberData::new("vi", ",", "."),
+    NumberData::new("ja", ".", ","),
+    NumberData::new("zh", ".", ","),
+    NumberData::new("ko", ".", ","),
+];
+
+/// Find the entry of a locale table for a language code
+/// Tries the full code first, then its primary language, so `fr-CA` finds `fr`
+pub(crate) fn find_locale<'a, T>(
+    table: &'a [T],
+    language: &str,
+    code: impl Fn(&T) -> &str,
+) -> Option<&'a T> {
+    let language = language.replace('_', "-");
+    let primary = language.split('-').next().unwrap_or_default();
+    table
+        .iter()
+        .find(|entry| code(entry).eq_ignore_ascii_case(&language))
+        .or_else(|| {
+            table
+                .iter()
+                .find(|entry| code(entry).eq_ignore_ascii_case(primary))
+        })
+}
+
+/// Symbols and conventions used to write numbers in a locale
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct NumberSymbols {
+    /// Decimal separator
+    pub decimal: String,
+
+    /// Grouping separator
+    pub group: String,
+
+    /// Size of the first group of integer digits, and of every following group
+    /// Usually `(3, 3)`; `(3, 2)` for the Indian system
+    pub grouping: (usize, usize),
+
+    /// Minimum number of digits before the first group separator can be used
+    pub minimum_grouping_digits: usize,
+
+    /// The ten digits of the locale's numbering system, from zero to nine
+    pub digits: String,
+
+    /// Minus sign
+    pub minus: String,
+
+    /// Exponent separator for scientific notation
+    pub exponent: String,
+
+    /// Pattern for percentages, where `{0}` is the number
+    pub percent: String,
+}
+
+impl Default for NumberSymbols {
+    fn default() -> Self {
+        Self::for_language("en")
+    }
+}
+
+impl NumberSymbols {
+    /// Get the built-in number symbols for a language code
+    /// Unknown languages use English conventions
+    ///
+    /// # Arguments
+    /// * `language` - Language code, such as `fr` or `de-CH`
+    pub fn for_language(language: &str) -> Self {
+        let data = find_locale(NUMBER_DATA, language, |d| d.code).unwrap_or(&NUMBER_DATA[0]);
+        Self {
+            decimal: data.decimal.to_string(),
+            group: data.group.to_string(),
+            grouping: data.grouping,
+            minimum_grouping_digits: data.minimum_grouping_digits,
+            digits: data.digits.to_string(),
+            minus: data.minus.to_string(),
+            exponent: "E".to_string(),
+            percent: data.percent.to_string(),
+        }
+    }
+}
+
+/// How a number is written
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum NumberStyle {
+    /// A decimal number, with up to 3 fraction digits
+    Decimal,
+
+    /// A whole number
+    Integer,
+
+    /// A fraction written as a percentage, so 0.25 is written as 25%
+    Percent,
+
+    /// Scientific notation, such as 1.235E3
+    Scientific,
+}
+
+impl NumberStyle {
+    /// Find a style by name; `integer`, `percent` or `scientific`, or anything else for decimal
+    ///
+    /// # Arguments
+    /// * `name` - Name of the style
+    pub fn from_name(name: &str) -> Self {
+        match name.trim() {
+            "integer" => Self::Integer,
+            "percent" => Self::Percent,
+            "scientific" => Self::Scientific,
+            _ => Self::Decimal,
+        }
+    }
+}
+
+/// Writes numbers using the conventions of a locale
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct NumberFormatter {
+    symbols: NumberSymbols,
+}
+
+impl NumberFormatter {
+    /// Create a formatter using the built-in conventions of a language
+    ///
+    /// # Arguments
+    /// * `language` - Language code, such as `fr` or `de-CH`
+    pub fn new(language: &str) -> Self {
+        Self::with_symbols(NumberSymbols::for_language(language))
+    }
+
+    /// Create a formatter using the given conventions
+    ///
+    /// # Arguments
+    /// * `symbols` - Number symbols to use
+    pub fn with_symbols(symbols: NumberSymbols) -> Self {
+        Self { symbols }
+    }
+
+    /// Return the conventions used by this formatter
+    pub fn symbols(&self) -> &NumberSymbols {
+        &self.symbols
+    }
+
+    /// Write a number in the given style
+    ///
+    /// # Arguments
+    /// * `value` - Number to write
+    /// * `style` - How to write the number
+    pub fn format(&self, value: f64, style: NumberStyle) -> String {
+        match style {
+            NumberStyle::Decimal => self.format_fixed(value, 0, 3),
+            NumberStyle::Integer => self.format_fixed(value, 0, 0),
+            NumberStyle::Percent => self
+                .symbols
+                .percent
+                .replace("{0}", &self.format_fixed(value * 100.0, 0, 0)),
+            NumberStyle::Scientific => self.format_scientific(value, 3),
+        }
+    }
+
+    /// Write a number with grouping, and between a minimum and maximum number of fraction digits
+    ///
+    /// # Arguments
+    /// * `value` - Number to write
+    /// * `minimum_fraction_digits` - Fraction digits always written, padding with zeros
+    /// * `maximum_fraction_digits` - Fraction digits the number is rounded to
+    pub fn format_fixed(
+        &self,
+        value: f64,
+        minimum_fraction_digits: usize,
+        maximum_fraction_digits: usize,
+    ) -> String {
+        if !value.is_finite() {
+            return self.format_special(value);
+        }
+
+        let maximum_fraction_digits = maximum_fraction_digits.max(minimum_fraction_digits);
+        let rounded = format!("{:.*}", maximum_fraction_digits, value.abs());
+        let (integer, fraction) = rounded.split_once('.').unwrap_or((&rounded, ""));
+        let keep = fraction
+            .trim_end_matches('0')
+            .len()
+            .max(minimum_fraction_digits);
+        let fraction = &fraction[..keep];
+
+        let negative = value < 0.0 && rounded.chars().any(|c| c.is_ascii_digit() && c != '0');
+        let mut output = String::new();
+        if negative {
+            output.push_str(&self.symbols.minus);
+        }
+        output.push_str(&self.group(integer));
+        if !fraction.is_empty() {
+            output.push_str(&self.symbols.decimal);
+            output.push_str(&self.digits(fraction));
+        }
+        output
+    }
+
+    /// Write a number in scientific notation
+    ///
+    /// # Arguments
+    /// * `value` - Number to write
+    /// * `maximum_fraction_digits` - Fraction digits the significand is rounded to
+    pub fn format_scientific(&self, value: f64, maximum_fraction_digits: usize) -> String {
+        if !value.is_finite() {
+            return self.format_special(value);
+        }
+
+        let formatted = format!("{:.*e}", maximum_fraction_digits, value);
+        let (significand, exponent) = formatted.split_once('e').unwrap_or((&formatted, "0"));
+        let significand: f64 = significand.parse().unwrap_or_default();
+        let exponent: i32 = exponent.parse().unwrap_or_default();
+
+        let mut output = self.format_fixed(significand, 0, maximum_fraction_digits);
+        output.push_str(&self.symbols.exponent);
+        if exponent < 0 {
+            output.push_str(&self.symbols.minus);
+        }
+        output.push_str(&self.digits(&exponent.unsigned_abs().to_string()));
+        output
+    }
+
+    fn format_special(&self, value: f64) -> String {
+        if value.is_nan() {
+            "NaN".to_string()
+        } else if value < 0.0 {
+            format!("{}∞", self.symbols.minus)
+        } else {
+            "∞".to_string()
+        }
+    }
+
+    /// Translate ASCII digits into the locale's numbering system
+    fn digits(&self, digits: &str) -> String {
+        let system: Vec<char> = self.symbols.digits.chars().collect();
+        digits
+            .chars()
+            .map(|c| match c.to_digit(10) {
+                Some(d) if system.len() == 10 => system[d as usize],
+                _ => c,
+            })
+            .collect()
+    }
+
+    /// Insert group separators into a string of ASCII digits
+    fn group(&self, integer: &str) -> String {
+        let (primary, secondary) = self.symbols.grouping;
+        if primary == 0 || integer.len() < primary + self.symbols.minimum_grouping_digits {
+            return self.digits(integer);
+        }
+
+        let mut groups = vec![&integer[integer.len() - primary..]];
+        let mut end = integer.len() - primary;
+        let secondary = secondary.max(1);
+        while end > 0 {
+            let start = end.saturating_sub(secondary);
+            groups.push(&integer[start..end]);
+            end = start;
+        }
+        groups.reverse();
+        self.digits(&groups.join("\u{0}"))
+            .replace('\u{0}', &self.symbols.group)
+    }
+}
+
+#[cfg(test)]
+mod test_token {
+    use super::*;
+
+    #[test]
+    fn test_for_language() {
+        assert_eq!(NumberSymbols::for_language("fr-CA").decimal, ",");
+        assert_eq!(NumberSymbols::for_language("de_CH").group, "’");
+        assert_eq!(NumberSymbols::for_language("xx"), NumberSymbols::default());
+    }
+
+    #[test]
+    fn test_decimal() {
+        let en = NumberFormatter::new("en");
+        assert_eq!(en.format(1234.5, NumberStyle::Decimal), "1,234.5");
+        assert_eq!(
+            en.format(-1234567.891, NumberStyle::Decimal),
+            "-1,234,567.891"
+        );
+        assert_eq!(en.format(0.12345, NumberStyle::Decimal), "0.123");
+        assert_eq!(en.format(-0.0001, NumberStyle::Decimal), "0");
+        assert_eq!(en.format(12.0, NumberStyle::Decimal), "12");
+
+        assert_eq!(
+            NumberFormatter::new("de").format(1234.5, NumberStyle::Decimal),
+            "1.234,5"
+        );
+        assert_eq!(
+            NumberFormatter::new("fr").format(1234.5, NumberStyle::Decimal),
+            "1\u{202F}234,5"
+        );
+        assert_eq!(
+            NumberFormatter::new("es").format(1234.5, NumberStyle::Decimal),
+            "1234,5"
+        );
+        assert_eq!(
+            NumberFormatter::new("es").format(12345.0, NumberStyle::Decimal),
+            "12.345"
+        );
+    }
+
+    #[test]
+    fn test_grouping() {
+        assert_eq!(
+            NumberFormatter::new("hi").format(12345678.0, NumberStyle::Integer),
+            "1,23,45,678"
+        );
+        assert_eq!(
+            NumberFormatter::new("ar").format(-1234.5, NumberStyle::Decimal),
+            "\u{61C}-١٬٢٣٤٫٥"
+        );
+        assert_eq!(
+            NumberFormatter::new("en").format(999.9, NumberStyle::Integer),
+            "1,000"
+        );
+    }
+
+    #[test]
+    fn test_fixed() {
+        let en = NumberFormatter::new("en");
+        assert_eq!(en.format_fixed(1.5, 2, 2), "1.50");
+        assert_eq!(en.format_fixed(1.0, 2, 4), "1.00");
+        assert_eq!(en.format_fixed(1.23456, 2, 4), "1.2346");
+        assert_eq!(en.format_fixed(1.2, 2, 4), "1.20");
+    }
+
+    #[test]
+    fn test_percent() {
+        assert_eq!(
+            NumberFormatter::new("en").format(0.256, NumberStyle::Percent),
+            "26%"
+        );
+        assert_eq!(
+            NumberFormatter::new("de").format(0.25, NumberStyle::Percent),
+            "25\u{A0}%"
+        );
+        assert_eq!(
+            NumberFormatter::new("tr").format(0.25, NumberStyle::Percent),
+            "%25"
+        );
+    }
+
+    #[test]
+    fn test_scientific() {
+        let en = NumberFormatter::new("en");
+        assert_eq!(en.format(1234.56, NumberStyle::Scientific), "1.235E3");
+        assert_eq!(en.format(0.00012, NumberStyle::Scientific), "1.2E-4");
+        assert_eq!(
+            NumberFormatter::new("fr").format(1234.56, NumberStyle::Scientific),
+            "1,235E3"
+        );
+        assert_eq!(en.format(f64::INFINITY, NumberStyle::Scientific), "∞");
+    }
+}
@@ -0,0 +1,327 @@
+use serde::{Deserialize, Serialize};
+
+/// Plural form of a word, as defined by the Unicode CLDR
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+#[serde(rename_all = "lowercase")]
+pub enum PluralCategory {
+    /// Form used for zero, in languages such as Arabic
+    Zero,
+
+    /// Form used for one, or numbers that behave like it
+    One,
+
+    /// Form used for two, in languages such as Arabic or Hebrew
+    Two,
+
+    /// Form used for small numbers, such as 2-4 in Russian
+    Few,
+
+    /// Form used for large numbers, such as 5-20 in Russian
+    Many,
+
+    /// Form used for everything else; every language has it
+    Other,
+}
+
+impl PluralCategory {
+    /// Find a category by name, such as `one` or `few`
+    ///
+    /// # Arguments
+    /// * `name` - Name of the category
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim() {
+            "zero" => Some(Self::Zero),
+            "one" => Some(Self::One),
+            "two" => Some(Self::Two),
+            "few" => Some(Self::Few),
+            "many" => Some(Self::Many),
+            "other" => Some(Self::Other),
+            _ => None,
+        }
+    }
+
+    /// Return the name of the category
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Zero => "zero",
+            Self::One => "one",
+            Self::Two => "two",
+            Self::Few => "few",
+            Self::Many => "many",
+            Self::Other => "other",
+        }
+    }
+}
+
+/// The operands plural rules are written in terms of
+/// `n` is the absolute value, `i` its integer digits, `v` the number of visible fraction digits
+/// and `f` the visible fraction digits
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct PluralOperands {
+    n: f64,
+    i: u64,
+    v: usize,
+    f: u64,
+}
+
+impl PluralOperands {
+    fn new(value: f64) -> Self {
+        let n = value.abs();
+        let written = n.to_string();
+        let fraction = written.split_once('.').map(|(_, f)| f).unwrap_or_default();
+        Self {
+            n,
+            i: n.trunc() as u64,
+            v: fraction.len(),
+            f: fraction.parse().unwrap_or_default(),
+        }
+    }
+
+    fn is_integer(&self) -> bool {
+        self.v == 0
+    }
+}
+
+/// Selects the plural form of a word for a number, following the rules of a language
+/// Covers both cardinal forms, used for counts, and ordinal forms, used for positions
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PluralRules {
+    language: String,
+}
+
+impl PluralRules {
+    /// Create the plural rules of a language
+    /// Unknown languages use English rules
+    ///
+    /// # Arguments
+    /// * `language` - Language code, such as `ru` or `pt-PT`
+    pub fn for_language(language: &str) -> Self {
+        Self {
+            language: language.replace('_', "-"),
+        }
+    }
+
+    /// Return the code of the language these rules are for
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    /// Select the plural form used for a count, as in "3 files"
+    ///
+    /// # Arguments
+    /// * `value` - Number being counted, as written
+    pub fn category(&self, value: f64) -> PluralCategory {
+        use PluralCategory::*;
+
+        let o = PluralOperands::new(value);
+        let (n, i) = (o.n, o.i);
+        let primary = self.language.split('-').next().unwrap_or_default();
+        match primary {
+            "ja" | "zh" | "ko" | "th" | "id" | "vi" | "ms" | "lo" | "my" => Other,
+
+            "pt" if self.language.eq_ignore_ascii_case("pt-PT") => match i {
+                1 if o.is_integer() => One,
+                _ => Other,
+            },
+            "fr" | "pt" if i <= 1 => One,
+            "hi" | "bn" | "fa" | "gu" | "kn" | "zu" | "am" if i == 0 || n == 1.0 => One,
+            "es" | "el" | "hu" | "tr" | "nb" | "no" | "bg" | "mr" | "ta" | "te" if n == 1.0 => One,
+            "da" if n == 1.0 || (o.f != 0 && i <= 1) => One,
+            "fr" | "pt" | "hi" | "bn" | "fa" | "gu" | "kn" | "zu" | "am" | "es" | "el" | "hu"
+            | "tr" | "nb" | "no" | "bg" | "mr" | "ta" | "te" | "da" => Other,
+
+            "ru" | "uk" | "be" => match (i % 10, i % 100) {
+                _ if !o.is_integer() => Other,
+                (1, m) if m != 11 => One,
+                (2..=4, m) if !(12..=14).contains(&m) => Few,
+                _ => Many,
+            },
+            "pl" => match (i % 10, i % 100) {
+                _ if !o.is_integer() => Other,
+                _ if i == 1 => One,
+                (2..=4, m) if !(12..=14).contains(&m) => Few,
+                _ => Many,
+            },
+            "cs" | "sk" => match i {
+                _ if !o.is_integer() => Many,
+                1 => One,
+                2..=4 => Few,
+                _ => Other,
+            },
+            "ro" => match i % 100 {
+                _ if i == 1 && o.is_integer() => One,
+                _ if !o.is_integer() || n == 0.0 => Few,
+                2..=19 => Few,
+                _ => Other,
+            },
+            "lt" => match (i % 10, i % 100) {
+                _ if !o.is_integer() => Many,
+                (1, m) if !(11..=19).contains(&m) => One,
+                (2..=9, m) if !(11..=19).contains(&m) => Few,
+                _ => Other,
+            },
+            "he" | "iw" => match i {
+                1 if o.is_integer() => One,
+                0 if !o.is_integer() => One,
+                2 if o.is_integer() => Two,
+                _ => Other,
+            },
+            "ar" => match i % 100 {
+                _ if !o.is_integer() => Other,
+                _ if i == 0 => Zero,
+                _ if i == 1 => One,
+                _ if i == 2 => Two,
+                3..=10 => Few,
+                11..=99 => Many,
+                _ => Other,
+            },
+            "cy" => match i {
+                _ if !o.is_integer() => Other,
+                0 => Zero,
+                1 => One,
+                2 => Two,
+                3 => Few,
+                6 => Many,
+                _ => Other,
+            },
+
+            _ if i == 1 && o.is_integer() => One,
+            _ => Other,
+        }
+    }
+
+    /// Select the ordinal form used for a position, as in "3rd place"
+    /// Languages without ordinal forms always use `other`
+    ///
+    /// # Arguments
+    /// * `value` - Position being written
+    pub fn ordinal(&self, value: i64) -> PluralCategory {
+        use PluralCategory::*;
+
+        let n = value.unsigned_abs();
+        let primary = self.language.split('-').next().unwrap_or_default();
+        match primary {
+            "en" => match (n % 10, n % 100) {
+                (1, m) if m != 11 => One,
+                (2, m) if m != 12 => Two,
+                (3, m) if m != 13 => Few,
+                _ => Other,
+            },
+            "sv" => match (n % 10, n % 100) {
+                (1 | 2, m) if m != 11 && m != 12 => One,
+                _ => Other,
+            },
+            "fr" | "ro" | "ms" | "vi" | "fil" | "ga" | "hy" if n == 1 => One,
+            "hu" if n == 1 || n == 5 => One,
+            "ne" if (1..=4).contains(&n) => One,
+            "it" if matches!(n, 8 | 11 | 80 | 800) => Many,
+            "uk" if n % 10 == 3 && n % 100 != 13 => Few,
+            "kk" if matches!(n % 10, 0 | 6 | 9) && n != 0 => Many,
+            "sq" => match (n % 10, n % 100) {
+                _ if n == 1 => One,
+                (4, m) if m != 14 => Many,
+                _ => Other,
+            },
+            "ca" => match n {
+                1 | 3 => One,
+                2 => Two,
+                4 => Few,
+                _ => Other,
+            },
+            "hi" | "gu" => match n {
+                1 => One,
+                2 | 3 => Two,
+                4 => Few,
+                6 => Many,
+                _ => Other,
+            },
+            "bn" | "as" => match n {
+                1 | 5 | 7..=10 => One,
+                2 | 3 => Two,
+                4 => Few,
+                6 => Many,
+                _ => Other,
+            },
+            "mk" => match (n % 10, n % 100) {
+                (1, m) if m != 11 => One,
+                (2, m) if m != 12 => Two,
+                (7 | 8, m) if m != 17 && m != 18 => Many,
+                _ => Other,
+            },
+            "cy" => match n {
+                0 | 7..=9 => Zero,
+                1 => One,
+                2 => Two,
+                3 | 4 => Few,
+                5 | 6 => Many,
+                _ => Other,
+            },
+            _ => Other,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_token {
+    use super::*;
+    use PluralCategory::*;
+
+    #[test]
+    fn test_operands() {
+        let o = PluralOperands::new(-1.25);
+        assert_eq!((o.n, o.i, o.v, o.f), (1.25, 1, 2, 25));
+        assert!(PluralOperands::new(3.0).is_integer());
+    }
+
+    #[test]
+    fn test_category() {
+        let en = PluralRules::for_language("en-US");
+        assert_eq!(en.category(1.0), One);
+        assert_eq!(en.category(0.0), Other);
+        assert_eq!(en.category(1.5), Other);
+
+        let fr = PluralRules::for_language("fr");
+        assert_eq!(fr.category(0.0), One);
+        assert_eq!(fr.category(1.5), One);
+        assert_eq!(fr.category(2.0), Other);
+
+        let ru = PluralRules::for_language("ru");
+        let forms: Vec<_> = [1.0, 2.0, 5.0, 11.0, 21.0, 22.0, 112.0, 1.5]
+            .into_iter()
+            .map(|n| ru.category(n))
+            .collect();
+        assert_eq!(forms, [One, Few, Many, Many, One, Few, Many, Other]);
+
+        let ar = PluralRules::for_language("ar");
+        assert_eq!(ar.category(0.0), Zero);
+        assert_eq!(ar.category(2.0), Two);
+        assert_eq!(ar.category(103.0), Few);
+        assert_eq!(ar.category(111.0), Many);
+        assert_eq!(ar.category(100.0), Other);
+
+        assert_eq!(PluralRules::for_language("ja").category(1.0), Other);
+        assert_eq!(PluralRules::for_language("pt-PT").category(0.0), Other);
+        assert_eq!(PluralRules::for_language("pt-PT").category(1.0), One);
+        assert_eq!(PluralRules::for_language("pt").category(0.0), One);
+    }
+
+    #[test]
+    fn test_ordinal() {
+        let en = PluralRules::for_language("en-GB");
+        let forms: Vec<_> = [1, 2, 3, 4, 11, 12, 13, 21, 22, 23, 101, 111]
+            .into_iter()
+            .map(|n| en.ordinal(n))
+            .collect();
+        assert_eq!(
+            forms,
+            [One, Two, Few, Other, Other, Other, Other, One, Two, Few, One, Other]
+        );
+
+        assert_eq!(PluralRules::for_language("fr").ordinal(1), One);
+        assert_eq!(PluralRules::for_language("fr").ordinal(2), Other);
+        assert_eq!(PluralRules::for_language("it").ordinal(11), Many);
+        assert_eq!(PluralRules::for_language("cy").ordinal(8), Zero);
+        assert_eq!(PluralRules::for_language("de").ordinal(1), Other);
+        assert_eq!(PluralRules::for_language("sv").ordinal(12), Other);
+    }
+}
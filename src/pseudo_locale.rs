@@ -0,0 +1,266 @@
+use crate::{Language, TextDirection};
+
+/// Settings for generating a pseudo-localized language
+#[derive(Clone, Debug, PartialEq)]
+pub struct PseudoLocaleOptions {
+    /// Full name of the generated language
+    pub name: String,
+
+    /// Language code of the generated language
+    pub short_name: String,
+
+    /// Replace ASCII letters with accented look-alikes
+    pub accents: bool,
+
+    /// Lengthen each string by this fraction of its length, such as 0.35 for 35%
+    pub expansion: f32,
+
+    /// Surround each string with `[` and `]` to reveal truncation
+    pub brackets: bool,
+
+    /// Reverse each string and mark it as right-to-left text
+    pub mirror: bool,
+}
+
+impl Default for PseudoLocaleOptions {
+    fn default() -> Self {
+        Self {
+            name: "Pseudo".to_string(),
+            short_name: "qps-ploc".to_string(),
+            accents: true,
+            expansion: 0.35,
+            brackets: true,
+            mirror: false,
+        }
+    }
+}
+
+impl PseudoLocaleOptions {
+    /// Options for a right-to-left pseudo-locale, using the `qps-plocm` code
+    pub fn mirrored() -> Self {
+        Self {
+            name: "Pseudo Mirrored".to_string(),
+            short_name: "qps-plocm".to_string(),
+            mirror: true,
+            ..Self::default()
+        }
+    }
+}
+
+const RIGHT_TO_LEFT_OVERRIDE: char = '\u{202E}';
+const POP_DIRECTIONAL_FORMATTING: char = '\u{202C}';
+const PADDING: &str = "ļöŕéɱ îþšûɱ ðöļöŕ šîţ åɱéţ ";
+
+/// Part of a string, either translatable text or a placeholder or markup to keep intact
+#[derive(Debug, PartialEq)]
+enum Segment<'a> {
+    Text(&'a str),
+    Protected(&'a str),
+}
+
+/// Split a string into text, and the placeholders, markup and entities within it
+fn segments(text: &str) -> Vec<Segment<'_>> {
+    let mut segments = vec![];
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let end = match c {
+            '{' => {
+                let mut depth = 0;
+                text[i..].char_indices().find_map(|(j, c)| {
+                    match c {
+                        '{' => depth += 1,
+                        '}' => depth -= 1,
+                        _ => {}
+                    }
+                    (depth == 0).then_some(i + j + 1)
+                })
+            }
+            '<' => text[i..].find('>').map(|j| i + j + 1),
+            '&' => text[i + 1..]
+                .find(|c: char| !c.is_ascii_alphanumeric() && c != '#')
+                .filter(|j| *j > 0 && text[i + 1 + j..].starts_with(';'))
+                .map(|j| i + j + 2),
+            _ => None,
+        };
+
+        if let Some(end) = end {
+            if start < i {
+                segments.push(Segment::Text(&text[start..i]));
+            }
+            segments.push(Segment::Protected(&text[i..end]));
+            start = end;
+            while chars.peek().is_some_and(|(j, _)| *j < end) {
+                chars.next();
+            }
+        }
+    }
+
+    if start < text.len() {
+        segments.push(Segment::Text(&text[start..]));
+    }
+    segments
+}
+
+fn accent(c: char) -> char {
+    let (plain, accented) = (
+        "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ",
+        "àƀçðéƒĝĥîĵķļɱñöþǫŕšţûṽŵẋýžÅƁÇÐÉƑĜĤÎĴĶĻṀÑÖÞǪŔŠŢÛṼŴẊÝŽ",
+    );
+    plain
+        .chars()
+        .position(|p| p == c)
+        .and_then(|i| accented.chars().nth(i))
+        .unwrap_or(c)
+}
+
+/// Pseudo-localize a single string, leaving placeholders such as `{name}`, markup and
+/// entities intact
+///
+/// # Arguments
+/// * `text` - String to transform
+/// * `options` - Transformations to apply
+pub fn pseudo_localize(text: &str, options: &PseudoLocaleOptions) -> String {
+    // Pairs of (text, is_protected)
+    let mut segments: Vec<(String, bool)> = segments(text)
+        .into_iter()
+        .map(|segment| match segment {
+            Segment::Protected(s) => (s.to_string(), true),
+            Segment::Text(s) if options.accents => (s.chars().map(accent).collect(), false),
+            Segment::Text(s) => (s.to_string(), false),
+        })
+        .collect();
+
+    let length = text.chars().count() as f32;
+    let padding = (length * options.expansion.max(0.0)).ceil() as usize;
+    if padding > 0 {
+        segments.push((PADDING.chars().cycle().take(padding).collect(), false));
+    }
+
+    let mut output: String = if options.mirror {
+        let reversed: String = segments
+            .iter()
+            .rev()
+            .map(|(s, protected)| {
+                if *protected {
+                    s.clone()
+                } else {
+                    s.chars().rev().collect()
+                }
+            })
+            .collect();
+        format!(
+            "{}{}{}",
+            RIGHT_TO_LEFT_OVERRIDE, reversed, POP_DIRECTIONAL_FORMATTING
+        )
+    } else {
+        segments.into_iter().map(|(s, _)| s).collect()
+    };
+
+    if options.brackets {
+        output = format!("[{}]", output);
+    }
+    output
+}
+
+impl Language {
+    /// Generate a pseudo-localized copy of this language, for testing layouts and finding
+    /// hard-coded strings before real translations are available
+    /// Mirrored copies are written right to left
+    /// # Arguments
+    /// * `options` - Transformations to apply, and the name and code of the new language
+    pub fn pseudo_localize(&self, options: &PseudoLocaleOptions) -> Language {
+        let mut language = self.clone();
+        language.set_names(&options.name, &options.short_name);
+        language.map_strings(|s| pseudo_localize(s, options));
+        if options.mirror {
+            language.metadata_mut().direction = Some(TextDirection::Rtl);
+        }
+        language
+    }
+}
+
+#[cfg(test)]
+mod test_token {
+    use super::*;
+    use crate as embedded_lang;
+    use crate::{embedded_language, LanguageSet};
+
+    fn plain() -> PseudoLocaleOptions {
+        PseudoLocaleOptions {
+            accents: false,
+            expansion: 0.0,
+            brackets: false,
+            ..PseudoLocaleOptions::default()
+        }
+    }
+
+    #[test]
+    fn test_segments() {
+        assert_eq!(
+            segments("Hi {name}, <b>{n, number}</b> &amp; more"),
+            vec![
+                Segment::Text("Hi "),
+                Segment::Protected("{name}"),
+                Segment::Text(", "),
+                Segment::Protected("<b>"),
+                Segment::Protected("{n, number}"),
+                Segment::Protected("</b>"),
+                Segment::Text(" "),
+                Segment::Protected("&amp;"),
+                Segment::Text(" more"),
+            ]
+        );
+        assert_eq!(segments("a & b {"), vec![Segment::Text("a & b {")]);
+    }
+
+    #[test]
+    fn test_accents() {
+        let options = PseudoLocaleOptions {
+            accents: true,
+            ..plain()
+        };
+        assert_eq!(pseudo_localize("Hello {name}!", &options), "Ĥéļļö {name}!");
+    }
+
+    #[test]
+    fn test_expansion() {
+        let options = PseudoLocaleOptions {
+            expansion: 0.4,
+            brackets: true,
+            ..plain()
+        };
+        assert_eq!(pseudo_localize("0123456789", &options), "[0123456789ļöŕé]");
+    }
+
+    #[test]
+    fn test_mirror() {
+        let options = PseudoLocaleOptions {
+            mirror: true,
+            ..plain()
+        };
+        assert_eq!(
+            pseudo_localize("abc {name} de", &options),
+            "\u{202E}ed {name} cba\u{202C}"
+        );
+    }
+
+    #[test]
+    fn test_pseudo_language() {
+        let lang = embedded_language!("../examples/en.lang.json");
+        let pseudo = lang.pseudo_localize(&PseudoLocaleOptions::default());
+        assert_eq!(pseudo.short_name(), "qps-ploc");
+        assert_eq!(pseudo.get("tree"), Some("[ţŕééļö]"));
+
+        let mut set = LanguageSet::new("en", &[lang]);
+        assert!(set.add_pseudo_language("en", &PseudoLocaleOptions::mirrored()));
+        assert!(!set.add_pseudo_language("fr", &PseudoLocaleOptions::default()));
+        set.add_language(pseudo);
+        assert!(set.set_language("qps-ploc"));
+        assert_eq!(set.get("category\\category2\\foo"), Some("[ƀàŕļö]"));
+        assert!(!set.is_rtl());
+        assert!(set.set_language("qps-plocm"));
+        assert_eq!(set.get("tree"), Some("[\u{202E}öļééŕţ\u{202C}]"));
+        assert!(set.is_rtl());
+    }
+}
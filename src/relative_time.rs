@@ -0,0 +1,612 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::{DateTime, NumberFormatter, PluralCategory, PluralRules, UnitPatterns};
+
+/// Units a relative time can be written in
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum RelativeUnit {
+    /// Seconds, as in 5 seconds ago
+    Second,
+
+    /// Minutes, as in in 5 minutes
+    Minute,
+
+    /// Hours, as in 3 hours ago
+    Hour,
+
+    /// Days, as in yesterday or in 2 days
+    Day,
+
+    /// Weeks, as in last week
+    Week,
+
+    /// Months of 30 days, as in next month
+    Month,
+
+    /// Years of 365 days, as in 2 years ago
+    Year,
+}
+
+impl RelativeUnit {
+    /// Find a unit by name, such as `day`
+    ///
+    /// # Arguments
+    /// * `name` - Name of the unit
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim() {
+            "second" => Some(Self::Second),
+            "minute" => Some(Self::Minute),
+            "hour" => Some(Self::Hour),
+            "day" => Some(Self::Day),
+            "week" => Some(Self::Week),
+            "month" => Some(Self::Month),
+            "year" => Some(Self::Year),
+            _ => None,
+        }
+    }
+
+    /// Return the number of seconds in the unit
+    pub fn seconds(&self) -> i64 {
+        match self {
+            Self::Second => 1,
+            Self::Minute => 60,
+            Self::Hour => 3600,
+            Self::Day => 86400,
+            Self::Week => 604800,
+            Self::Month => 2592000,
+            Self::Year => 31536000,
+        }
+    }
+
+    /// Pick the unit best suited to write a number of seconds
+    ///
+    /// # Arguments
+    /// * `seconds` - Distance in time, in seconds
+    pub fn best_for(seconds: i64) -> Self {
+        match seconds.unsigned_abs() {
+            0..45 => Self::Second,
+            45..2700 => Self::Minute,
+            2700..79200 => Self::Hour,
+            79200..518400 => Self::Day,
+            518400..2419200 => Self::Week,
+            2419200..28512000 => Self::Month,
+            _ => Self::Year,
+        }
+    }
+}
+
+/// Whether relative times may use names such as "yesterday" instead of numbers
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RelativeNumeric {
+    /// Always use a number, as in 1 day ago
+    Always,
+
+    /// Use a name where the language has one, as in yesterday
+    Auto,
+}
+
+/// Phrases for a relative time unit
+/// Patterns use `{0}` for the number; names are keyed by offset, such as `-1` for yesterday
+#[derive(Serialize, Deserialize, Clone, Debug, Default, Eq, PartialEq)]
+pub struct RelativeUnitPatterns {
+    /// Patterns for the past by plural category, such as `{0} days ago`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub past: UnitPatterns,
+
+    /// Patterns for the future by plural category, such as `in {0} days`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub future: UnitPatterns,
+
+    /// Names for specific offsets, such as `-1` for yesterday or `0` for today
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub named: HashMap<String, String>,
+}
+
+impl RelativeUnitPatterns {
+    fn parse(past: &str, future: &str, named: &str) -> Self {
+        let forms = |forms: &str| {
+            forms
+                .split('|')
+                .filter(|form| !form.is_empty())
+                .map(|form| match form.split_once(':') {
+                    Some((key, value)) => (key.to_string(), value.to_string()),
+                    None => ("other".to_string(), form.to_string()),
+                })
+                .collect::<HashMap<_, _>>()
+        };
+        let plural = |s: &str| {
+            forms(s)
+                .into_iter()
+                .filter_map(|(k, v)| PluralCategory::from_name(&k).map(|c| (c, v)))
+                .collect()
+        };
+        Self {
+            past: plural(past),
+            future: plural(future),
+            named: forms(named),
+        }
+    }
+
+    /// Replace the phrases given in another set of patterns
+    fn extend(&mut self, other: &Self) {
+        self.past
+            .extend(other.past.iter().map(|(k, v)| (*k, v.clone())));
+        self.future
+            .extend(other.future.iter().map(|(k, v)| (*k, v.clone())));
+        self.named
+            .extend(other.named.iter().map(|(k, v)| (k.clone(), v.clone())));
+    }
+}
+
+/// Relative time phrases for a locale, from the Unicode CLDR
+/// Entries are `category:pattern` pairs separated by `|`, and names are `offset:name` pairs
+const RELATIVE_DATA: &[(&str, RelativeUnit, &str, &str, &str)] = &[
+    (
+        "en",
+        RelativeUnit::Second,
+        "one:{0} second ago|other:{0} seconds ago",
+        "one:in {0} second|other:in {0} seconds",
+        "0:now",
+    ),
+    (
+        "en",
+        RelativeUnit::Minute,
+        "one:{0} minute ago|other:{0} minutes ago",
+        "one:in {0} minute|other:in {0} minutes",
+        "",
+    ),
+    (
+        "en",
+        RelativeUnit::Hour,
+        "one:{0} hour ago|other:{0} hours ago",
+        "one:in {0} hour|other:in {0} hours",
+        "",
+    ),
+    (
+        "en",
+        RelativeUnit::Day,
+        "one:{0} day ago|other:{0} days ago",
+        "one:in {0} day|other:in {0} days",
+        "-1:yesterday|0:today|1:tomorrow",
+    ),
+    (
+        "en",
+        RelativeUnit::Week,
+        "one:{0} week ago|other:{0} weeks ago",
+        "one:in {0} week|other:in {0} weeks",
+        "-1:last week|0:this week|1:next week",
+    ),
+    (
+        "en",
+        RelativeUnit::Month,
+        "one:{0} month ago|other:{0} months ago",
+        "one:in {0} month|other:in {0} months",
+        "-1:last month|0:this month|1:next month",
+    ),
+    (
+        "en",
+        RelativeUnit::Year,
+        "one:{0} year ago|other:{0} years ago",
+        "one:in {0} year|other:in {0} years",
+        "-1:last year|0:this year|1:next year",
+    ),
+    (
+        "fr",
+        RelativeUnit::Second,
+        "one:il y a {0} seconde|other:il y a {0} secondes",
+        "one:dans {0} seconde|other:dans {0} secondes",
+        "0:maintenant",
+    ),
+    (
+        "fr",
+        RelativeUnit::Minute,
+        "one:il y a {0} minute|other:il y a {0} minutes",
+        "one:dans {0} minute|other:dans {0} minutes",
+        "",
+    ),
+    (
+        "fr",
+        RelativeUnit::Hour,
+        "one:il y a {0} heure|other:il y a {0} heures",
+        "one:dans {0} heure|other:dans {0} heures",
+        "",
+    ),
+    (
+        "fr",
+        RelativeUnit::Day,
+        "one:il y a {0} jour|other:il y a {0} jours",
+        "one:dans {0} jour|other:dans {0} jours",
+        "-2:avant-hier|-1:hier|0:aujourd’hui|1:demain|2:après-demain",
+    ),
+    (
+        "fr",
+        RelativeUnit::Week,
+        "one:il y a {0} semaine|other:il y a {0} semaines",
+        "one:dans {0} semaine|other:dans {0} semaines",
+        "-1:la semaine dernière|0:cette semaine|1:la semaine prochaine",
+    ),
+    (
+        "fr",
+        RelativeUnit::Month,
+        "il y a {0} mois",
+        "dans {0} mois",
+        "-1:le mois dernier|0:ce mois-ci|1:le mois prochain",
+    ),
+    (
+        "fr",
+        RelativeUnit::Year,
+        "one:il y a {0} an|other:il y a {0} ans",
+        "one:dans {0} an|other:dans {0} ans",
+        "-1:l’année dernière|0:cette année|1:l’année prochaine",
+    ),
+    (
+        "de",
+        RelativeUnit::Second,
+        "one:vor {0} Sekunde|other:vor {0} Sekunden",
+        "one:in {0} Sekunde|other:in {0} Sekunden",
+        "0:jetzt",
+    ),
+    (
+        "de",
+        RelativeUnit::Minute,
+        "one:vor {0} Minute|other:vor {0} Minuten",
+        "one:in {0} Minute|other:in {0} Minuten",
+        "",
+    ),
+    (
+        "de",
+        RelativeUnit::Hour,
+        "one:vor {0} Stunde|other:vor {0} Stunden",
+        "one:in {0} Stunde|other:in {0} Stunden",
+        "",
+    ),
+    (
+        "de",
+        RelativeUnit::Day,
+        "one:vor {0} Tag|other:vor {0} Tagen",
+        "one:in {0} Tag|other:in {0} Tagen",
+        "-2:vorgestern|-1:gestern|0:heute|1:morgen|2:übermorgen",
+    ),
+    (
+        "de",
+        RelativeUnit::Week,
+        "one:vor {0} Woche|other:vor {0} Wochen",
+        "one:in {0} Woche|other:in {0} Wochen",
+        "-1:letzte Woche|0:diese Woche|1:nächste Woche",
+    ),
+    (
+        "de",
+        RelativeUnit::Month,
+        "one:vor {0} Monat|other:vor {0} Monaten",
+        "one:in {0} Monat|other:in {0} Monaten",
+        "-1:letzten Monat|0:diesen Monat|1:nächsten Monat",
+    ),
+    (
+        "de",
+        RelativeUnit::Year,
+        "one:vor {0} Jahr|other:vor {0} Jahren",
+        "one:in {0} Jahr|other:in {0} Jahren",
+        "-1:letztes Jahr|0:dieses Jahr|1:nächstes Jahr",
+    ),
+    (
+        "es",
+        RelativeUnit::Second,
+        "one:hace {0} segundo|other:hace {0} segundos",
+        "one:dentro de {0} segundo|other:dentro de {0} segundos",
+        "0:ahora",
+    ),
+    (
+        "es",
+        RelativeUnit::Minute,
+        "one:hace {0} minuto|other:hace {0} minutos",
+        "one:dentro de {0} minuto|other:dentro de {0} minutos",
+        "",
+    ),
+    (
+        "es",
+        RelativeUnit::Hour,
+        "one:hace {0} hora|other:hace {0} horas",
+        "one:dentro de {0} hora|other:dentro de {0} horas",
+        "",
+    ),
+    (
+        "es",
+        RelativeUnit::Day,
+        "one:hace {0} día|other:hace {0} días",
+        "one:dentro de {0} día|other:dentro de {0} días",
+        "-2:anteayer|-1:ayer|0:hoy|1:mañana|2:pasado mañana",
+    ),
+    (
+        "es",
+        RelativeUnit::Week,
+        "one:hace {0} semana|other:hace {0} semanas",
+        "one:dentro de {0} semana|other:dentro de {0} semanas",
+        "-1:la semana pasada|0:esta semana|1:la próxima semana",
+    ),
+    (
+        "es",
+        RelativeUnit::Month,
+        "one:hace {0} mes|other:hace {0} meses",
+        "one:dentro de {0} mes|other:dentro de {0} meses",
+        "-1:el mes pasado|0:este mes|1:el próximo mes",
+    ),
+    (
+        "es",
+        RelativeUnit::Year,
+        "one:hace {0} año|other:hace {0} años",
+        "one:dentro de {0} año|other:dentro de {0} años",
+        "-1:el año pasado|0:este año|1:el próximo año",
+    ),
+    (
+        "ru",
+        RelativeUnit::Second,
+        "one:{0} секунду назад|few:{0} секунды назад|many:{0} секунд назад|other:{0} секунды назад",
+        "one:через {0} секунду|few:через {0} секунды|many:через {0} секунд|other:через {0} секунды",
+        "0:сейчас",
+    ),
+    (
+        "ru",
+        RelativeUnit::Minute,
+        "one:{0} минуту назад|few:{0} минуты назад|many:{0} минут назад|other:{0} минуты назад",
+        "one:через {0} минуту|few:через {0} минуты|many:через {0} минут|other:через {0} минуты",
+        "",
+    ),
+    (
+        "ru",
+        RelativeUnit::Hour,
+        "one:{0} час назад|few:{0} часа назад|many:{0} часов назад|other:{0} часа назад",
+        "one:через {0} час|few:через {0} часа|many:через {0} часов|other:через {0} часа",
+        "",
+    ),
+    (
+        "ru",
+        RelativeUnit::Day,
+        "one:{0} день назад|few:{0} дня назад|many:{0} дней назад|other:{0} дня назад",
+        "one:через {0} день|few:через {0} дня|many:через {0} дней|other:через {0} дня",
+        "-2:позавчера|-1:вчера|0:сегодня|1:завтра|2:послезавтра",
+    ),
+    (
+        "ru",
+        RelativeUnit::Week,
+        "one:{0} неделю назад|few:{0} недели назад|many:{0} недель назад|other:{0} недели назад",
+        "one:через {0} неделю|few:через {0} недели|many:через {0} недель|other:через {0} недели",
+        "-1:на прошлой неделе|0:на этой неделе|1:на следующей неделе",
+    ),
+    (
+        "ru",
+        RelativeUnit::Month,
+        "one:{0} месяц назад|few:{0} месяца назад|many:{0} месяцев назад|other:{0} месяца назад",
+        "one:через {0} месяц|few:через {0} месяца|many:через {0} месяцев|other:через {0} месяца",
+        "-1:в прошлом месяце|0:в этом месяце|1:в следующем месяце",
+    ),
+    (
+        "ru",
+        RelativeUnit::Year,
+        "one:{0} год назад|few:{0} года назад|many:{0} лет назад|other:{0} года назад",
+        "one:через {0} год|few:через {0} года|many:через {0} лет|other:через {0} года",
+        "-1:в прошлом году|0:в этом году|1:в следующем году",
+    ),
+];
+
+/// Writes distances in time, such as "3 minutes ago" or "tomorrow", using the conventions of a locale
+#[derive(Clone, Debug, PartialEq)]
+pub struct RelativeTimeFormatter {
+    numbers: NumberFormatter,
+    rules: PluralRules,
+    units: HashMap<RelativeUnit, RelativeUnitPatterns>,
+}
+
+impl Default for RelativeTimeFormatter {
+    fn default() -> Self {
+        Self::new("en")
+    }
+}
+
+impl RelativeTimeFormatter {
+    /// Create a formatter using the built-in phrases of a language
+    /// Languages without built-in phrases use English ones, unless their language file provides some
+    ///
+    /// # Arguments
+    /// * `language` - Language code, such as `fr` or `de-AT`
+    pub fn new(language: &str) -> Self {
+        Self::with_numbers(language, NumberFormatter::new(language))
+    }
+
+    /// Create a formatter using the built-in phrases of a language, and the given number formatter
+    ///
+    /// # Arguments
+    /// * `language` - Language code, such as `fr` or `de-AT`
+    /// * `numbers` - Number formatter to write numbers with
+    pub fn with_numbers(language: &str, numbers: NumberFormatter) -> Self {
+        let primary = language.split(['-', '_']).next().unwrap_or_default();
+        let code = if RELATIVE_DATA
+            .iter()
+            .any(|(code, ..)| code.eq_ignore_ascii_case(primary))
+        {
+            primary
+        } else {
+            "en"
+        };
+
+        Self {
+            numbers,
+            rules: PluralRules::for_language(language),
+            units: RELATIVE_DATA
+                .iter()
+                .filter(|(c, ..)| c.eq_ignore_ascii_case(code))
+                .map(|(_, unit, past, future, named)| {
+                    (*unit, RelativeUnitPatterns::parse(past, future, named))
+                })
+                .collect(),
+        }
+    }
+
+    /// Replace the plural rules used to choose the form of a phrase, such as those of another language
+    ///
+    /// # Arguments
+    /// * `rules` - Plural rules to use
+    pub fn with_plural_rules(mut self, rules: PluralRules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// Replace the phrases used for some units
+    ///
+    /// # Arguments
+    /// * `overrides` - Phrases by unit
+    pub fn with_overrides(
+        mut self,
+        overrides: &HashMap<RelativeUnit, RelativeUnitPatterns>,
+    ) -> Self {
+        for (unit, patterns) in overrides {
+            self.units.entry(*unit).or_default().extend(patterns);
+        }
+        self
+    }
+
+    /// Write a distance in time in the given unit
+    /// Negative values are in the past, and positive values in the future
+    ///
+    /// # Arguments
+    /// * `value` - Distance in time
+    /// * `unit` - Unit of the distance
+    /// * `numeric` - Whether names such as "yesterday" may be used
+    pub fn format(&self, value: f64, unit: RelativeUnit, numeric: RelativeNumeric) -> String {
+        let patterns = self.units.get(&unit);
+        if numeric == RelativeNumeric::Auto && value.fract() == 0.0 {
+            let offset = (value as i64).to_string();
+            if let Some(name) = patterns.and_then(|p| p.named.get(&offset)) {
+                return name.clone();
+            }
+        }
+
+        let number = self.numbers.format_fixed(value.abs(), 0, 3);
+        let category = self.rules.category(value.abs());
+        let forms = patterns.map(|p| {
+            if value < 0.0 || (value == 0.0 && value.is_sign_negative()) {
+                &p.past
+            } else {
+                &p.future
+            }
+        });
+        forms
+            .and_then(|f| f.get(&category).or_else(|| f.get(&PluralCategory::Other)))
+            .map(|pattern| pattern.replace("{0}", &number))
+            .unwrap_or(number)
+    }
+
+    /// Write a distance in time, in the unit best suited to it
+    ///
+    /// # Arguments
+    /// * `seconds` - Distance in time; negative in the past, positive in the future
+    /// * `numeric` - Whether names such as "yesterday" may be used
+    pub fn format_seconds(&self, seconds: i64, numeric: RelativeNumeric) -> String {
+        let unit = RelativeUnit::best_for(seconds);
+        let value = (seconds as f64 / unit.seconds() as f64).round();
+        self.format(if value == 0.0 { 0.0 } else { value }, unit, numeric)
+    }
+
+    /// Write the distance from one date to another, such as "3 days ago"
+    ///
+    /// # Arguments
+    /// * `date` - Date to describe
+    /// * `now` - Date the distance is measured from
+    /// * `numeric` - Whether names such as "yesterday" may be used
+    pub fn format_date(&self, date: &DateTime, now: &DateTime, numeric: RelativeNumeric) -> String {
+        self.format_seconds(date.unix_timestamp() - now.unix_timestamp(), numeric)
+    }
+}
+
+#[cfg(test)]
+mod test_token {
+    use super::*;
+
+    #[test]
+    fn test_best_for() {
+        assert_eq!(RelativeUnit::best_for(-30), RelativeUnit::Second);
+        assert_eq!(RelativeUnit::best_for(180), RelativeUnit::Minute);
+        assert_eq!(RelativeUnit::best_for(-7200), RelativeUnit::Hour);
+        assert_eq!(RelativeUnit::best_for(86400 * 2), RelativeUnit::Day);
+        assert_eq!(RelativeUnit::best_for(86400 * 10), RelativeUnit::Week);
+        assert_eq!(RelativeUnit::best_for(86400 * 60), RelativeUnit::Month);
+        assert_eq!(RelativeUnit::best_for(86400 * 400), RelativeUnit::Year);
+    }
+
+    #[test]
+    fn test_format() {
+        let en = RelativeTimeFormatter::new("en");
+        assert_eq!(
+            en.format(-3.0, RelativeUnit::Minute, RelativeNumeric::Always),
+            "3 minutes ago"
+        );
+        assert_eq!(
+            en.format(1.0, RelativeUnit::Day, RelativeNumeric::Always),
+            "in 1 day"
+        );
+        assert_eq!(
+            en.format(-1.0, RelativeUnit::Day, RelativeNumeric::Auto),
+            "yesterday"
+        );
+        assert_eq!(
+            en.format(2.0, RelativeUnit::Day, RelativeNumeric::Auto),
+            "in 2 days"
+        );
+        assert_eq!(
+            en.format_seconds(10, RelativeNumeric::Auto),
+            "in 10 seconds"
+        );
+        assert_eq!(en.format_seconds(0, RelativeNumeric::Auto), "now");
+        assert_eq!(
+            en.format_seconds(-86400, RelativeNumeric::Auto),
+            "yesterday"
+        );
+
+        let ru = RelativeTimeFormatter::new("ru");
+        assert_eq!(
+            ru.format(-5.0, RelativeUnit::Hour, RelativeNumeric::Always),
+            "5 часов назад"
+        );
+        assert_eq!(
+            ru.format(2.0, RelativeUnit::Day, RelativeNumeric::Always),
+            "через 2 дня"
+        );
+        assert_eq!(
+            RelativeTimeFormatter::new("de-AT").format(
+                2.0,
+                RelativeUnit::Day,
+                RelativeNumeric::Auto
+            ),
+            "übermorgen"
+        );
+
+        let now = DateTime::new(2025, 6, 1).unwrap();
+        let then = DateTime::new(2025, 5, 1).unwrap();
+        assert_eq!(
+            en.format_date(&then, &now, RelativeNumeric::Auto),
+            "last month"
+        );
+    }
+
+    #[test]
+    fn test_overrides() {
+        let overrides = serde_json::from_str(
+            r#"{"day": {"past": {"one": "{0} päev tagasi", "other": "{0} päeva tagasi"}, "named": {"-1": "eile"}}}"#,
+        )
+        .unwrap();
+        let et = RelativeTimeFormatter::new("et").with_overrides(&overrides);
+        assert_eq!(
+            et.format(-3.0, RelativeUnit::Day, RelativeNumeric::Auto),
+            "3 päeva tagasi"
+        );
+        assert_eq!(
+            et.format(-1.0, RelativeUnit::Day, RelativeNumeric::Auto),
+            "eile"
+        );
+        assert_eq!(
+            et.format(3.0, RelativeUnit::Day, RelativeNumeric::Auto),
+            "in 3 days"
+        );
+    }
+}
@@ -0,0 +1,135 @@
+use serde::{Deserialize, Serialize};
+
+use crate::serialization::content_hash;
+
+/// MIME types of common resources, by file extension
+const MIME_TYPES: &[(&str, &str)] = &[
+    ("css", "text/css"),
+    ("csv", "text/csv"),
+    ("gif", "image/gif"),
+    ("htm", "text/html"),
+    ("html", "text/html"),
+    ("jpeg", "image/jpeg"),
+    ("jpg", "image/jpeg"),
+    ("json", "application/json"),
+    ("md", "text/markdown"),
+    ("mp3", "audio/mpeg"),
+    ("ogg", "audio/ogg"),
+    ("pdf", "application/pdf"),
+    ("png", "image/png"),
+    ("svg", "image/svg+xml"),
+    ("ttf", "font/ttf"),
+    ("txt", "text/plain"),
+    ("wav", "audio/wav"),
+    ("webp", "image/webp"),
+    ("woff", "font/woff"),
+    ("woff2", "font/woff2"),
+    ("xml", "application/xml"),
+];
+
+/// Leading bytes of common binary formats, and their MIME types
+const MAGIC_NUMBERS: &[(&[u8], &str)] = &[
+    (b"\x89PNG\r\n\x1a\n", "image/png"),
+    (b"\xff\xd8\xff", "image/jpeg"),
+    (b"GIF87a", "image/gif"),
+    (b"GIF89a", "image/gif"),
+    (b"%PDF-", "application/pdf"),
+    (b"wOFF", "font/woff"),
+    (b"wOF2", "font/woff2"),
+];
+
+/// Description of a resource embedded in a language
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct ResourceInfo {
+    /// Name of the resource
+    pub name: String,
+
+    /// Code of the language the resource was found in
+    pub language: String,
+
+    /// MIME type of the resource, guessed from its name and contents
+    pub mime_type: String,
+
+    /// Size of the resource, in bytes
+    pub size: usize,
+
+    /// Hash of the contents of the resource, to detect when it changes
+    pub hash: String,
+}
+
+impl ResourceInfo {
+    /// Describe a resource
+    ///
+    /// # Arguments
+    /// * `name` - Name of the resource
+    /// * `language` - Code of the language the resource belongs to
+    /// * `bytes` - Contents of the resource
+    pub fn new(name: &str, language: &str, bytes: &[u8]) -> Self {
+        Self {
+            name: name.to_string(),
+            language: language.to_string(),
+            mime_type: guess_mime_type(name, bytes).to_string(),
+            size: bytes.len(),
+            hash: content_hash(bytes.iter().copied()),
+        }
+    }
+}
+
+/// Guess the MIME type of a resource from the extension of its name, then from its contents
+/// Unrecognized text is `text/plain`, and anything else `application/octet-stream`
+///
+/// # Arguments
+/// * `name` - Name of the resource, such as `help.pdf`
+/// * `bytes` - Contents of the resource
+pub fn guess_mime_type(name: &str, bytes: &[u8]) -> &'static str {
+    let extension = name
+        .rsplit_once('.')
+        .map(|(_, extension)| extension)
+        .unwrap_or_default();
+    if let Some((_, mime_type)) = MIME_TYPES
+        .iter()
+        .find(|(e, _)| e.eq_ignore_ascii_case(extension))
+    {
+        return mime_type;
+    }
+
+    match MAGIC_NUMBERS
+        .iter()
+        .find(|(magic, _)| bytes.starts_with(magic))
+    {
+        Some((_, mime_type)) => mime_type,
+        None if std::str::from_utf8(bytes).is_ok() => "text/plain",
+        None => "application/octet-stream",
+    }
+}
+
+#[cfg(test)]
+mod test_token {
+    use super::*;
+
+    #[test]
+    fn test_guess_mime_type() {
+        assert_eq!(guess_mime_type("help.PDF", b""), "application/pdf");
+        assert_eq!(
+            guess_mime_type("logo", b"\x89PNG\r\n\x1a\n...."),
+            "image/png"
+        );
+        assert_eq!(guess_mime_type("license_file", b"MIT"), "text/plain");
+        assert_eq!(
+            guess_mime_type("blob", &[0xff, 0x00, 0xfe]),
+            "application/octet-stream"
+        );
+    }
+
+    #[test]
+    fn test_resource_info() {
+        let info = ResourceInfo::new("notes.txt", "fr", b"bonjour");
+        assert_eq!(info.mime_type, "text/plain");
+        assert_eq!(info.size, 7);
+        assert_eq!(info.hash, ResourceInfo::new("other", "en", b"bonjour").hash);
+        assert_ne!(
+            info.hash,
+            ResourceInfo::new("notes.txt", "fr", b"salut").hash
+        );
+    }
+}
@@ -0,0 +1,232 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::de::{self, Deserializer};
+use serde::ser::{SerializeMap, Serializer};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+
+/// Version of the serialized language format written by this crate
+pub const FORMAT_VERSION: u32 = 1;
+
+/// Marker for the format version field of serialized languages and sets
+/// Always serializes as the current version, and refuses to load newer versions
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) struct FormatVersion;
+
+impl Serialize for FormatVersion {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u32(FORMAT_VERSION)
+    }
+}
+
+impl<'de> Deserialize<'de> for FormatVersion {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let version = u32::deserialize(deserializer)?;
+        if version > FORMAT_VERSION {
+            Err(de::Error::custom(format!(
+                "unsupported format version {}; expected at most {}",
+                version, FORMAT_VERSION
+            )))
+        } else {
+            Ok(FormatVersion)
+        }
+    }
+}
+
+const DATA_URI_PREFIX: &str = "data:";
+const BASE64_MARKER: &str = ";base64,";
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum EncodedResource {
+    Bytes(Vec<u8>),
+    Encoded(String),
+}
+
+/// A resource of a language, either embedded or declared by a path not yet loaded
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum Resource {
+    /// Contents of the resource
+    Embedded(Vec<u8>),
+
+    /// Path to the resource, relative to the language file that declared it
+    File(String),
+}
+
+/// Join the path of a declared resource to the directory it is relative to
+/// Fails for absolute paths, and for paths leaving the directory, such as `../secret.txt`
+///
+/// # Arguments
+/// * `directory` - Directory containing the language file
+/// * `path` - Path of the resource, as written in the language
+pub(crate) fn resource_path(directory: &Path, path: &str) -> Result<PathBuf, String> {
+    let relative = Path::new(path);
+    if relative
+        .components()
+        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+    {
+        Ok(directory.join(relative))
+    } else {
+        Err(format!(
+            "{}: resource paths must be inside the language's directory",
+            path
+        ))
+    }
+}
+
+/// Encode a resource as a base64 data URI
+pub(crate) fn encode_resource(bytes: &[u8]) -> String {
+    format!(
+        "{}application/octet-stream{}{}",
+        DATA_URI_PREFIX,
+        BASE64_MARKER,
+        STANDARD.encode(bytes)
+    )
+}
+
+/// Decode a resource from a base64 data URI
+pub(crate) fn decode_resource(uri: &str) -> Result<Vec<u8>, String> {
+    match uri
+        .strip_prefix(DATA_URI_PREFIX)
+        .and_then(|uri| uri.split_once(BASE64_MARKER))
+    {
+        Some((_, data)) => STANDARD.decode(data).map_err(|e| e.to_string()),
+        None => Err("resource is not a base64 data URI".to_string()),
+    }
+}
+
+/// Hash bytes with the 64-bit FNV-1a hash, written in hexadecimal
+/// Unlike the standard library's hashers, the result is stable between versions and platforms
+pub(crate) fn content_hash<I: IntoIterator<Item = u8>>(bytes: I) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in bytes {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{:016x}", hash)
+}
+
+/// Serde adapter storing resources as base64 data URIs, and declared resources as their paths
+/// Resources stored as arrays of bytes are still accepted when loading
+pub(crate) mod resources {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        resources: &HashMap<String, Resource>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(resources.len()))?;
+        for (name, resource) in resources {
+            match resource {
+                Resource::Embedded(bytes) => map.serialize_entry(name, &encode_resource(bytes))?,
+                Resource::File(path) => map.serialize_entry(name, path)?,
+            }
+        }
+        map.end()
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<HashMap<String, Resource>, D::Error> {
+        HashMap::<String, EncodedResource>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(name, resource)| match resource {
+                EncodedResource::Bytes(bytes) => Ok((name, Resource::Embedded(bytes))),
+                EncodedResource::Encoded(uri) if uri.starts_with(DATA_URI_PREFIX) => {
+                    decode_resource(&uri)
+                        .map(|bytes| (name, Resource::Embedded(bytes)))
+                        .map_err(de::Error::custom)
+                }
+                EncodedResource::Encoded(path) => Ok((name, Resource::File(path))),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test_token {
+    use super::*;
+    use crate as embedded_lang;
+    use crate::{embedded_language, Language, LanguageSet};
+
+    fn test_language() -> Language {
+        let mut lang = embedded_language!("../examples/en.lang.json", resources = [
+            "license_file": "../LICENSE"
+        ]);
+        lang.attach("numbers", vec![1, 2, 3]).unwrap();
+        lang
+    }
+
+    #[test]
+    fn test_encode_resource() {
+        let uri = encode_resource(b"hello");
+        assert_eq!(uri, "data:application/octet-stream;base64,aGVsbG8=");
+        assert_eq!(decode_resource(&uri).unwrap(), b"hello");
+        assert!(decode_resource("aGVsbG8=").is_err());
+    }
+
+    #[test]
+    fn test_language_round_trip() {
+        let lang = test_language();
+        let json = serde_json::to_string(&lang).unwrap();
+        let loaded = Language::new_from_string(&json, HashMap::default()).unwrap();
+
+        assert_eq!(loaded, lang);
+        assert_eq!(
+            loaded.attachment::<Vec<i32>>("numbers"),
+            Some(vec![1, 2, 3])
+        );
+        assert_eq!(
+            loaded.binary_resource("license_file"),
+            lang.binary_resource("license_file")
+        );
+    }
+
+    #[test]
+    fn test_set_round_trip() {
+        let mut set = LanguageSet::new(
+            "fr",
+            &[
+                test_language(),
+                embedded_language!("../examples/fr.lang.json"),
+            ],
+        );
+        set.set_fallback_language("en");
+        set.add_layer("overrides", embedded_language!("../examples/fr.lang.json"));
+        set.add_namespace("plugin_x", &[test_language()]);
+
+        let json = serde_json::to_string(&set).unwrap();
+        let loaded: LanguageSet = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded, set);
+    }
+
+    #[test]
+    fn test_format_version() {
+        let json = serde_json::to_value(test_language()).unwrap();
+        assert_eq!(json["format_version"], FORMAT_VERSION);
+
+        let json =
+            r#"{"format_version": 9999, "name": "English", "short_name": "en", "strings": {}}"#;
+        assert!(Language::new_from_string(json, HashMap::default()).is_err());
+    }
+
+    #[test]
+    fn test_legacy_resources() {
+        let json = r#"{"name": "English", "short_name": "en", "strings": {}, "resources": {"a": [104, 105]}}"#;
+        let lang = Language::new_from_string(json, HashMap::default()).unwrap();
+        assert_eq!(lang.utf8_resource("a"), Some("hi"));
+    }
+
+    #[test]
+    fn test_new_from_string_keeps_resources() {
+        let json = serde_json::to_string(&test_language()).unwrap();
+        let lang = Language::new_from_string(
+            &json,
+            HashMap::from([("extra".to_string(), b"extra".to_vec())]),
+        )
+        .unwrap();
+
+        assert!(lang.binary_resource("license_file").is_some());
+        assert_eq!(lang.utf8_resource("extra"), Some("extra"));
+    }
+}
@@ -0,0 +1,101 @@
+use serde::{Deserialize, Serialize};
+
+/// Information for translators about a string, stored in a language's `notes` section by path
+/// Notes are usually written in the fallback language, and apply to every translation of the string
+#[derive(Serialize, Deserialize, Clone, Debug, Default, Eq, PartialEq)]
+pub struct StringNotes {
+    /// What the string means, or how it is used
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Where the string appears, such as `button` or `settings dialog title`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<String>,
+
+    /// Longest allowed translation, in characters
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<usize>,
+
+    /// Name or location of a screenshot showing the string
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub screenshot: Option<String>,
+
+    /// True if the string must be left as it is written in the fallback language, such as a brand name
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub do_not_translate: bool,
+}
+
+impl StringNotes {
+    /// Return true if no notes are given
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Fill in anything left out of these notes from another set of notes
+    ///
+    /// # Arguments
+    /// * `other` - Notes to take missing values from
+    pub fn or(&self, other: &StringNotes) -> StringNotes {
+        StringNotes {
+            description: self.description.clone().or(other.description.clone()),
+            context: self.context.clone().or(other.context.clone()),
+            max_length: self.max_length.or(other.max_length),
+            screenshot: self.screenshot.clone().or(other.screenshot.clone()),
+            do_not_translate: self.do_not_translate || other.do_not_translate,
+        }
+    }
+
+    /// Check a translation against the constraints in these notes
+    /// Returns a description of the first problem found, if any
+    ///
+    /// # Arguments
+    /// * `text` - Translated text
+    /// * `original` - Text in the fallback language, if there is one
+    pub fn check(&self, text: &str, original: Option<&str>) -> Option<String> {
+        let length = text.chars().count();
+        match (self.max_length, original) {
+            (Some(max_length), _) if length > max_length => Some(format!(
+                "{} characters long, over the limit of {}",
+                length, max_length
+            )),
+            (_, Some(original)) if self.do_not_translate && text != original => {
+                Some("translated, but marked as do not translate".to_string())
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_token {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let notes: StringNotes =
+            serde_json::from_str(r#"{"context": "button", "max_length": 5}"#).unwrap();
+        assert_eq!(notes.check("Save", None), None);
+        assert_eq!(notes.check("Sauvé", None), None);
+        assert_eq!(
+            notes.check("Speichern", None).unwrap(),
+            "9 characters long, over the limit of 5"
+        );
+
+        let brand = StringNotes {
+            do_not_translate: true,
+            ..Default::default()
+        };
+        assert!(brand.check("Acme", Some("Acme")).is_none());
+        assert!(brand.check("Acmé", Some("Acme")).is_some());
+        assert!(brand.check("Acmé", None).is_none());
+
+        let combined = notes.or(&brand);
+        assert_eq!(combined.context.as_deref(), Some("button"));
+        assert!(combined.do_not_translate);
+        assert!(StringNotes::default().is_empty());
+        assert_eq!(
+            serde_json::to_string(&StringNotes::default()).unwrap(),
+            "{}"
+        );
+    }
+}
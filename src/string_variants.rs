@@ -0,0 +1,201 @@
+use serde::{de, Deserialize, Deserializer, Serialize};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::PluralCategory;
+
+/// Selector used when no variant matches
+pub(crate) const OTHER_SELECTOR: &str = "other";
+
+/// Variants of a string chosen by a selector, such as the grammatical gender of the user
+/// Written in language files as `{"$select": {"male": "He", "female": "She", "other": "They"}}`
+/// The `other` variant is required
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SelectStrings {
+    #[serde(rename = "$select", deserialize_with = "deserialize_variants")]
+    variants: HashMap<String, String>,
+}
+
+impl SelectStrings {
+    /// Create a set of variants
+    /// Fails if there is no `other` variant
+    ///
+    /// # Arguments
+    /// * `variants` - Text of each variant, by selector
+    pub fn new(variants: HashMap<String, String>) -> Result<Self, String> {
+        check_other(&variants)?;
+        Ok(Self { variants })
+    }
+
+    /// Check that there is an `other` variant, which editing the variants may have removed
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        check_other(&self.variants)
+    }
+
+    /// Return the text of every variant, by selector
+    pub fn variants(&self) -> &HashMap<String, String> {
+        &self.variants
+    }
+
+    /// Return the text of every variant, by selector, for editing
+    pub fn variants_mut(&mut self) -> &mut HashMap<String, String> {
+        &mut self.variants
+    }
+
+    /// Return every selector other than `other`
+    pub fn selectors(&self) -> impl Iterator<Item = &str> {
+        self.variants
+            .keys()
+            .map(String::as_str)
+            .filter(|s| *s != OTHER_SELECTOR)
+    }
+
+    /// Return the `other` variant, used when no selector matches
+    pub fn other(&self) -> Option<&str> {
+        self.variants.get(OTHER_SELECTOR).map(String::as_str)
+    }
+
+    /// Return the variant for a selector, or the `other` variant if there is none
+    ///
+    /// # Arguments
+    /// * `selector` - Selector to find, such as `female`
+    pub fn get(&self, selector: &str) -> Option<&str> {
+        self.variants
+            .get(selector)
+            .map(String::as_str)
+            .or_else(|| self.other())
+    }
+}
+
+/// Variants of a string chosen by the ordinal form of a position, as in "1st" and "2nd"
+/// Written in language files as `{"$ordinal": {"one": "{n}st", "two": "{n}nd", "few": "{n}rd", "other": "{n}th"}}`
+/// The `other` variant is required
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct OrdinalStrings {
+    #[serde(rename = "$ordinal", deserialize_with = "deserialize_variants")]
+    variants: HashMap<PluralCategory, String>,
+}
+
+impl OrdinalStrings {
+    /// Create a set of variants
+    /// Fails if there is no `other` variant
+    ///
+    /// # Arguments
+    /// * `variants` - Text of each variant, by ordinal category
+    pub fn new(variants: HashMap<PluralCategory, String>) -> Result<Self, String> {
+        check_other(&variants)?;
+        Ok(Self { variants })
+    }
+
+    /// Check that there is an `other` variant, which editing the variants may have removed
+    pub(crate) fn validate(&self) -> Result<(), String> {
+        check_other(&self.variants)
+    }
+
+    /// Return the text of every variant, by ordinal category
+    pub fn variants(&self) -> &HashMap<PluralCategory, String> {
+        &self.variants
+    }
+
+    /// Return the text of every variant, by ordinal category, for editing
+    pub fn variants_mut(&mut self) -> &mut HashMap<PluralCategory, String> {
+        &mut self.variants
+    }
+
+    /// Return the `other` variant, used when no category matches
+    pub fn other(&self) -> Option<&str> {
+        self.variants
+            .get(&PluralCategory::Other)
+            .map(String::as_str)
+    }
+
+    /// Return the variant for an ordinal category, or the `other` variant if there is none
+    ///
+    /// # Arguments
+    /// * `category` - Category to find, as selected by [`crate::PluralRules::ordinal`]
+    pub fn get(&self, category: PluralCategory) -> Option<&str> {
+        self.variants
+            .get(&category)
+            .map(String::as_str)
+            .or_else(|| self.other())
+    }
+}
+
+/// Variant keys that can name the `other` variant
+trait VariantKey: Eq + Hash + Sized {
+    fn other() -> Self;
+}
+
+impl VariantKey for String {
+    fn other() -> Self {
+        OTHER_SELECTOR.to_string()
+    }
+}
+
+impl VariantKey for PluralCategory {
+    fn other() -> Self {
+        PluralCategory::Other
+    }
+}
+
+fn check_other<K: VariantKey>(variants: &HashMap<K, String>) -> Result<(), String> {
+    if variants.contains_key(&K::other()) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Variants must include an {} variant",
+            OTHER_SELECTOR
+        ))
+    }
+}
+
+/// Deserialize variants, rejecting those without an `other` variant
+fn deserialize_variants<'de, D: Deserializer<'de>, K: VariantKey + Deserialize<'de>>(
+    deserializer: D,
+) -> Result<HashMap<K, String>, D::Error> {
+    let variants = HashMap::<K, String>::deserialize(deserializer)?;
+    check_other(&variants).map_err(de::Error::custom)?;
+    Ok(variants)
+}
+
+#[cfg(test)]
+mod test_token {
+    use super::*;
+
+    #[test]
+    fn test_select_strings() {
+        let select: SelectStrings =
+            serde_json::from_str(r#"{"$select": {"male": "He", "other": "They"}}"#).unwrap();
+        assert_eq!(select.get("male"), Some("He"));
+        assert_eq!(select.get("female"), Some("They"));
+        assert_eq!(select.other(), Some("They"));
+        assert_eq!(select.selectors().collect::<Vec<_>>(), vec!["male"]);
+
+        assert!(serde_json::from_str::<SelectStrings>(r#"{"$select": {}, "a": "b"}"#).is_err());
+        assert!(
+            SelectStrings::new(HashMap::from([("male".to_string(), "He".to_string())])).is_err()
+        );
+        assert!(serde_json::from_str::<SelectStrings>(r#"{"$select": {"male": "He"}}"#).is_err());
+    }
+
+    #[test]
+    fn test_ordinal_strings() {
+        let ordinal: OrdinalStrings = serde_json::from_str(
+            r#"{"$ordinal": {"one": "{n}st", "two": "{n}nd", "other": "{n}th"}}"#,
+        )
+        .unwrap();
+        assert_eq!(ordinal.get(PluralCategory::Two), Some("{n}nd"));
+        assert_eq!(ordinal.get(PluralCategory::Few), Some("{n}th"));
+
+        assert!(serde_json::from_str::<OrdinalStrings>(r#"{"$ordinal": {"third": "x"}}"#).is_err());
+        assert!(
+            serde_json::from_str::<OrdinalStrings>(r#"{"$ordinal": {"one": "{n}st"}}"#).is_err()
+        );
+        assert!(
+            OrdinalStrings::new(HashMap::from([(PluralCategory::One, "{n}st".to_string())]))
+                .is_err()
+        );
+    }
+}
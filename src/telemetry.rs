@@ -0,0 +1,191 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// A single string lookup made through a language set
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct LookupEvent<'a> {
+    /// Key of the string
+    pub key: &'a str,
+
+    /// Language code the string was requested in
+    pub language: &'a str,
+
+    /// Language code the string was found in, if it was found
+    pub served_by: Option<&'a str>,
+
+    /// True if the string was served by the fallback language instead of the requested one
+    pub fallback: bool,
+}
+
+/// Function called for every lookup, such as to forward lookups to `log` or `tracing`
+pub type LookupHook = Arc<dyn Fn(&LookupEvent) + Send + Sync>;
+
+/// Lookup counts for a single key
+#[derive(Serialize, Deserialize, Clone, Debug, Default, Eq, PartialEq)]
+pub struct KeyTelemetry {
+    /// Number of lookups of the key
+    pub lookups: u64,
+
+    /// Number of lookups served by the fallback language
+    pub fallback: u64,
+
+    /// Number of lookups that found nothing
+    pub missed: u64,
+}
+
+/// Lookup counts collected by a telemetry instance
+#[derive(Serialize, Deserialize, Clone, Debug, Default, Eq, PartialEq)]
+pub struct TelemetrySnapshot {
+    /// Number of lookups
+    pub lookups: u64,
+
+    /// Number of lookups served by the fallback language
+    pub fallback: u64,
+
+    /// Number of lookups that found nothing
+    pub missed: u64,
+
+    /// Lookup counts for each key
+    pub keys: HashMap<String, KeyTelemetry>,
+
+    /// Number of lookups served by each language
+    pub languages: HashMap<String, u64>,
+}
+
+/// Opt-in instrumentation counting the lookups made through a language set
+/// Clones share the same counts, so a clone can be kept to read the results later
+#[derive(Clone, Default)]
+pub struct LookupTelemetry {
+    counts: Arc<Mutex<TelemetrySnapshot>>,
+    hook: Option<LookupHook>,
+}
+
+impl LookupTelemetry {
+    /// Create a new telemetry instance with no lookups recorded
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Call a function for every lookup recorded
+    ///
+    /// # Arguments
+    /// * `hook` - Function called with each lookup
+    pub fn with_hook<F: Fn(&LookupEvent) + Send + Sync + 'static>(mut self, hook: F) -> Self {
+        self.hook = Some(Arc::new(hook));
+        self
+    }
+
+    /// Record a lookup
+    ///
+    /// # Arguments
+    /// * `event` - Lookup to record
+    pub fn record(&self, event: &LookupEvent) {
+        if let Ok(mut counts) = self.counts.lock() {
+            let missed = event.served_by.is_none();
+            counts.lookups += 1;
+            counts.fallback += event.fallback as u64;
+            counts.missed += missed as u64;
+
+            let key = counts.keys.entry(event.key.to_string()).or_default();
+            key.lookups += 1;
+            key.fallback += event.fallback as u64;
+            key.missed += missed as u64;
+
+            if let Some(language) = event.served_by {
+                *counts.languages.entry(language.to_string()).or_default() += 1;
+            }
+        }
+
+        if let Some(hook) = &self.hook {
+            hook(event);
+        }
+    }
+
+    /// Return a copy of the counts recorded so far
+    pub fn snapshot(&self) -> TelemetrySnapshot {
+        self.counts.lock().map(|c| c.clone()).unwrap_or_default()
+    }
+
+    /// Reset every count to zero
+    pub fn reset(&self) {
+        if let Ok(mut counts) = self.counts.lock() {
+            *counts = TelemetrySnapshot::default();
+        }
+    }
+}
+
+impl fmt::Debug for LookupTelemetry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LookupTelemetry")
+            .field("counts", &self.counts)
+            .field("hook", &self.hook.is_some())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test_token {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_record() {
+        let telemetry = LookupTelemetry::new();
+        telemetry.record(&LookupEvent {
+            key: "tree",
+            language: "fr",
+            served_by: Some("fr"),
+            fallback: false,
+        });
+        telemetry.record(&LookupEvent {
+            key: "mustard",
+            language: "fr",
+            served_by: Some("en"),
+            fallback: true,
+        });
+        telemetry.record(&LookupEvent {
+            key: "nope",
+            language: "fr",
+            served_by: None,
+            fallback: false,
+        });
+
+        let snapshot = telemetry.snapshot();
+        assert_eq!(snapshot.lookups, 3);
+        assert_eq!(snapshot.fallback, 1);
+        assert_eq!(snapshot.missed, 1);
+        assert_eq!(snapshot.keys["mustard"].fallback, 1);
+        assert_eq!(snapshot.keys["nope"].missed, 1);
+        assert_eq!(snapshot.languages["en"], 1);
+        assert_eq!(snapshot.languages["fr"], 1);
+
+        let json = serde_json::to_string(&snapshot).unwrap();
+        assert_eq!(
+            serde_json::from_str::<TelemetrySnapshot>(&json).unwrap(),
+            snapshot
+        );
+
+        telemetry.reset();
+        assert_eq!(telemetry.snapshot(), TelemetrySnapshot::default());
+    }
+
+    #[test]
+    fn test_hook() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let hook_calls = calls.clone();
+        let telemetry = LookupTelemetry::new().with_hook(move |e| {
+            assert_eq!(e.key, "tree");
+            hook_calls.fetch_add(1, Ordering::SeqCst);
+        });
+
+        telemetry.record(&LookupEvent {
+            key: "tree",
+            language: "fr",
+            served_by: Some("fr"),
+            fallback: false,
+        });
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+}
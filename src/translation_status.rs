@@ -0,0 +1,128 @@
+use serde::{Deserialize, Serialize};
+
+use crate::serialization::content_hash;
+use crate::LanguageStringObject;
+
+/// How far along the translation of a string is
+#[derive(
+    Serialize, Deserialize, Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Ord, PartialOrd,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum TranslationState {
+    /// Not translated yet; the string may be a copy of the source text
+    #[default]
+    Untranslated,
+
+    /// Translated by machine, and not checked by a person
+    Machine,
+
+    /// Translated, but possibly out of date or uncertain
+    Fuzzy,
+
+    /// Checked by a translator
+    Reviewed,
+
+    /// Accepted for release
+    Approved,
+}
+
+impl TranslationState {
+    /// Find a state by name, such as `fuzzy` or `approved`
+    ///
+    /// # Arguments
+    /// * `name` - Name of the state
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim() {
+            "untranslated" => Some(Self::Untranslated),
+            "machine" => Some(Self::Machine),
+            "fuzzy" => Some(Self::Fuzzy),
+            "reviewed" => Some(Self::Reviewed),
+            "approved" => Some(Self::Approved),
+            _ => None,
+        }
+    }
+}
+
+/// The translation status of a string, stored in a language's `status` section by path
+#[derive(Serialize, Deserialize, Clone, Debug, Default, Eq, PartialEq)]
+pub struct TranslationStatus {
+    /// How far along the translation is
+    #[serde(default)]
+    pub state: TranslationState,
+
+    /// Hash of the source text the string was translated from, as given by [`source_hash`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_hash: Option<String>,
+}
+
+impl TranslationStatus {
+    /// Create a status for a string translated from a source string
+    ///
+    /// # Arguments
+    /// * `state` - How far along the translation is
+    /// * `source` - String in the fallback language, if there is one
+    pub fn new(state: TranslationState, source: Option<&LanguageStringObject>) -> Self {
+        Self {
+            state,
+            source_hash: source.map(source_hash),
+        }
+    }
+
+    /// Return true if the source string changed since the string was translated
+    /// Strings without a recorded source hash are never stale
+    ///
+    /// # Arguments
+    /// * `source` - String in the fallback language
+    pub fn is_stale(&self, source: &LanguageStringObject) -> bool {
+        self.source_hash
+            .as_ref()
+            .is_some_and(|hash| *hash != source_hash(source))
+    }
+}
+
+/// Hash the text of a string, including every variant, to detect when it changes
+/// The hash is a 64-bit FNV-1a hash written in hexadecimal, and is stable between versions
+///
+/// # Arguments
+/// * `source` - String to hash
+pub fn source_hash(source: &LanguageStringObject) -> String {
+    let mut texts = source.texts();
+    texts.sort();
+
+    content_hash(texts.join("\u{1f}").bytes())
+}
+
+#[cfg(test)]
+mod test_token {
+    use super::*;
+
+    #[test]
+    fn test_source_hash() {
+        let tree = LanguageStringObject::Direct("tree".to_string());
+        assert_eq!(
+            source_hash(&LanguageStringObject::Direct(String::new())),
+            "cbf29ce484222325"
+        );
+        assert_eq!(source_hash(&tree), source_hash(&tree.clone()));
+        assert_ne!(
+            source_hash(&tree),
+            source_hash(&LanguageStringObject::Direct("trees".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_is_stale() {
+        let tree = LanguageStringObject::Direct("tree".to_string());
+        let status = TranslationStatus::new(TranslationState::Reviewed, Some(&tree));
+        assert!(!status.is_stale(&tree));
+        assert!(status.is_stale(&LanguageStringObject::Direct("oak".to_string())));
+        assert!(!TranslationStatus::default().is_stale(&tree));
+
+        let status: TranslationStatus = serde_json::from_str(r#"{"state": "fuzzy"}"#).unwrap();
+        assert_eq!(status.state, TranslationState::Fuzzy);
+        assert_eq!(
+            TranslationState::from_name("approved"),
+            Some(TranslationState::Approved)
+        );
+    }
+}
@@ -0,0 +1,839 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::{NumberFormatter, PluralCategory, PluralRules};
+
+/// Units of measurement that can be written with a number
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Unit {
+    /// Bytes
+    Byte,
+
+    /// Thousands of bytes
+    Kilobyte,
+
+    /// Millions of bytes
+    Megabyte,
+
+    /// Billions of bytes
+    Gigabyte,
+
+    /// Trillions of bytes
+    Terabyte,
+
+    /// Seconds
+    Second,
+
+    /// Minutes
+    Minute,
+
+    /// Hours
+    Hour,
+
+    /// Days
+    Day,
+
+    /// Weeks
+    Week,
+
+    /// Centimeters
+    Centimeter,
+
+    /// Meters
+    Meter,
+
+    /// Kilometers
+    Kilometer,
+
+    /// Miles
+    Mile,
+}
+
+const UNITS: &[(Unit, &str)] = &[
+    (Unit::Byte, "byte"),
+    (Unit::Kilobyte, "kilobyte"),
+    (Unit::Megabyte, "megabyte"),
+    (Unit::Gigabyte, "gigabyte"),
+    (Unit::Terabyte, "terabyte"),
+    (Unit::Second, "second"),
+    (Unit::Minute, "minute"),
+    (Unit::Hour, "hour"),
+    (Unit::Day, "day"),
+    (Unit::Week, "week"),
+    (Unit::Centimeter, "centimeter"),
+    (Unit::Meter, "meter"),
+    (Unit::Kilometer, "kilometer"),
+    (Unit::Mile, "mile"),
+];
+
+impl Unit {
+    /// Find a unit by its CLDR name, such as `kilometer`
+    ///
+    /// # Arguments
+    /// * `name` - Name of the unit
+    pub fn from_name(name: &str) -> Option<Self> {
+        UNITS
+            .iter()
+            .find(|(_, n)| *n == name.trim())
+            .map(|(unit, _)| *unit)
+    }
+
+    /// Return the CLDR name of the unit, such as `kilometer`
+    pub fn name(&self) -> &'static str {
+        UNITS
+            .iter()
+            .find(|(unit, _)| unit == self)
+            .map(|(_, name)| *name)
+            .unwrap_or_default()
+    }
+}
+
+/// How much space a unit is written in
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum UnitWidth {
+    /// Written out, such as 5 kilometers
+    Long,
+
+    /// Abbreviated, such as 5 km
+    Short,
+
+    /// Abbreviated without spacing, such as 5km
+    Narrow,
+}
+
+impl UnitWidth {
+    /// Find a width by name; `long`, `narrow`, or anything else for short
+    ///
+    /// # Arguments
+    /// * `name` - Name of the width
+    pub fn from_name(name: &str) -> Self {
+        match name.trim() {
+            "long" => Self::Long,
+            "narrow" => Self::Narrow,
+            _ => Self::Short,
+        }
+    }
+}
+
+/// Unit patterns for a locale, where `{0}` is the number
+/// Each entry is either a single pattern, or `category:pattern` pairs separated by `|`
+/// An empty locale is used when a language has no patterns of its own
+const UNIT_DATA: &[(&str, UnitWidth, Unit, &str)] = &[
+    ("", UnitWidth::Short, Unit::Byte, "{0} B"),
+    ("", UnitWidth::Short, Unit::Kilobyte, "{0} kB"),
+    ("", UnitWidth::Short, Unit::Megabyte, "{0} MB"),
+    ("", UnitWidth::Short, Unit::Gigabyte, "{0} GB"),
+    ("", UnitWidth::Short, Unit::Terabyte, "{0} TB"),
+    ("", UnitWidth::Short, Unit::Second, "{0} s"),
+    ("", UnitWidth::Short, Unit::Minute, "{0} min"),
+    ("", UnitWidth::Short, Unit::Hour, "{0} h"),
+    ("", UnitWidth::Short, Unit::Day, "{0} d"),
+    ("", UnitWidth::Short, Unit::Week, "{0} w"),
+    ("", UnitWidth::Short, Unit::Centimeter, "{0} cm"),
+    ("", UnitWidth::Short, Unit::Meter, "{0} m"),
+    ("", UnitWidth::Short, Unit::Kilometer, "{0} km"),
+    ("", UnitWidth::Short, Unit::Mile, "{0} mi"),
+    ("en", UnitWidth::Short, Unit::Second, "{0} sec"),
+    ("en", UnitWidth::Short, Unit::Minute, "{0} min"),
+    ("en", UnitWidth::Short, Unit::Hour, "{0} hr"),
+    (
+        "en",
+        UnitWidth::Short,
+        Unit::Day,
+        "one:{0} day|other:{0} days",
+    ),
+    (
+        "en",
+        UnitWidth::Short,
+        Unit::Week,
+        "one:{0} wk|other:{0} wks",
+    ),
+    (
+        "en",
+        UnitWidth::Long,
+        Unit::Byte,
+        "one:{0} byte|other:{0} bytes",
+    ),
+    (
+        "en",
+        UnitWidth::Long,
+        Unit::Kilobyte,
+        "one:{0} kilobyte|other:{0} kilobytes",
+    ),
+    (
+        "en",
+        UnitWidth::Long,
+        Unit::Megabyte,
+        "one:{0} megabyte|other:{0} megabytes",
+    ),
+    (
+        "en",
+        UnitWidth::Long,
+        Unit::Gigabyte,
+        "one:{0} gigabyte|other:{0} gigabytes",
+    ),
+    (
+        "en",
+        UnitWidth::Long,
+        Unit::Terabyte,
+        "one:{0} terabyte|other:{0} terabytes",
+    ),
+    (
+        "en",
+        UnitWidth::Long,
+        Unit::Second,
+        "one:{0} second|other:{0} seconds",
+    ),
+    (
+        "en",
+        UnitWidth::Long,
+        Unit::Minute,
+        "one:{0} minute|other:{0} minutes",
+    ),
+    (
+        "en",
+        UnitWidth::Long,
+        Unit::Hour,
+        "one:{0} hour|other:{0} hours",
+    ),
+    (
+        "en",
+        UnitWidth::Long,
+        Unit::Day,
+        "one:{0} day|other:{0} days",
+    ),
+    (
+        "en",
+        UnitWidth::Long,
+        Unit::Week,
+        "one:{0} week|other:{0} weeks",
+    ),
+    (
+        "en",
+        UnitWidth::Long,
+        Unit::Centimeter,
+        "one:{0} centimeter|other:{0} centimeters",
+    ),
+    (
+        "en",
+        UnitWidth::Long,
+        Unit::Meter,
+        "one:{0} meter|other:{0} meters",
+    ),
+    (
+        "en",
+        UnitWidth::Long,
+        Unit::Kilometer,
+        "one:{0} kilometer|other:{0} kilometers",
+    ),
+    (
+        "en",
+        UnitWidth::Long,
+        Unit::Mile,
+        "one:{0} mile|other:{0} miles",
+    ),
+    (
+        "en-GB",
+        UnitWidth::Long,
+        Unit::Centimeter,
+        "one:{0} centimetre|other:{0} centimetres",
+    ),
+    (
+        "en-GB",
+        UnitWidth::Long,
+        Unit::Meter,
+        "one:{0} metre|other:{0} metres",
+    ),
+    (
+        "en-GB",
+        UnitWidth::Long,
+        Unit::Kilometer,
+        "one:{0} kilometre|other:{0} kilometres",
+    ),
+    ("fr", UnitWidth::Short, Unit::Byte, "{0} o"),
+    ("fr", UnitWidth::Short, Unit::Kilobyte, "{0} ko"),
+    ("fr", UnitWidth::Short, Unit::Megabyte, "{0} Mo"),
+    ("fr", UnitWidth::Short, Unit::Gigabyte, "{0} Go"),
+    ("fr", UnitWidth::Short, Unit::Terabyte, "{0} To"),
+    ("fr", UnitWidth::Short, Unit::Day, "{0} j"),
+    ("fr", UnitWidth::Short, Unit::Week, "{0} sem."),
+    (
+        "fr",
+        UnitWidth::Long,
+        Unit::Byte,
+        "one:{0} octet|other:{0} octets",
+    ),
+    (
+        "fr",
+        UnitWidth::Long,
+        Unit::Kilobyte,
+        "one:{0} kilooctet|other:{0} kilooctets",
+    ),
+    (
+        "fr",
+        UnitWidth::Long,
+        Unit::Megabyte,
+        "one:{0} mégaoctet|other:{0} mégaoctets",
+    ),
+    (
+        "fr",
+        UnitWidth::Long,
+        Unit::Gigabyte,
+        "one:{0} gigaoctet|other:{0} gigaoctets",
+    ),
+    (
+        "fr",
+        UnitWidth::Long,
+        Unit::Terabyte,
+        "one:{0} téraoctet|other:{0} téraoctets",
+    ),
+    (
+        "fr",
+        UnitWidth::Long,
+        Unit::Second,
+        "one:{0} seconde|other:{0} secondes",
+    ),
+    (
+        "fr",
+        UnitWidth::Long,
+        Unit::Minute,
+        "one:{0} minute|other:{0} minutes",
+    ),
+    (
+        "fr",
+        UnitWidth::Long,
+        Unit::Hour,
+        "one:{0} heure|other:{0} heures",
+    ),
+    (
+        "fr",
+        UnitWidth::Long,
+        Unit::Day,
+        "one:{0} jour|other:{0} jours",
+    ),
+    (
+        "fr",
+        UnitWidth::Long,
+        Unit::Week,
+        "one:{0} semaine|other:{0} semaines",
+    ),
+    (
+        "fr",
+        UnitWidth::Long,
+        Unit::Centimeter,
+        "one:{0} centimètre|other:{0} centimètres",
+    ),
+    (
+        "fr",
+        UnitWidth::Long,
+        Unit::Meter,
+        "one:{0} mètre|other:{0} mètres",
+    ),
+    (
+        "fr",
+        UnitWidth::Long,
+        Unit::Kilometer,
+        "one:{0} kilomètre|other:{0} kilomètres",
+    ),
+    (
+        "fr",
+        UnitWidth::Long,
+        Unit::Mile,
+        "one:{0} mile|other:{0} miles",
+    ),
+    ("de", UnitWidth::Short, Unit::Hour, "{0} Std."),
+    ("de", UnitWidth::Short, Unit::Day, "{0} Tg."),
+    ("de", UnitWidth::Short, Unit::Week, "{0} W."),
+    ("de", UnitWidth::Short, Unit::Second, "{0} Sek."),
+    ("de", UnitWidth::Short, Unit::Minute, "{0} Min."),
+    ("de", UnitWidth::Long, Unit::Byte, "{0} Byte"),
+    ("de", UnitWidth::Long, Unit::Kilobyte, "{0} Kilobyte"),
+    ("de", UnitWidth::Long, Unit::Megabyte, "{0} Megabyte"),
+    ("de", UnitWidth::Long, Unit::Gigabyte, "{0} Gigabyte"),
+    ("de", UnitWidth::Long, Unit::Terabyte, "{0} Terabyte"),
+    (
+        "de",
+        UnitWidth::Long,
+        Unit::Second,
+        "one:{0} Sekunde|other:{0} Sekunden",
+    ),
+    (
+        "de",
+        UnitWidth::Long,
+        Unit::Minute,
+        "one:{0} Minute|other:{0} Minuten",
+    ),
+    (
+        "de",
+        UnitWidth::Long,
+        Unit::Hour,
+        "one:{0} Stunde|other:{0} Stunden",
+    ),
+    (
+        "de",
+        UnitWidth::Long,
+        Unit::Day,
+        "one:{0} Tag|other:{0} Tage",
+    ),
+    (
+        "de",
+        UnitWidth::Long,
+        Unit::Week,
+        "one:{0} Woche|other:{0} Wochen",
+    ),
+    ("de", UnitWidth::Long, Unit::Centimeter, "{0} Zentimeter"),
+    ("de", UnitWidth::Long, Unit::Meter, "{0} Meter"),
+    ("de", UnitWidth::Long, Unit::Kilometer, "{0} Kilometer"),
+    (
+        "de",
+        UnitWidth::Long,
+        Unit::Mile,
+        "one:{0} Meile|other:{0} Meilen",
+    ),
+    (
+        "es",
+        UnitWidth::Long,
+        Unit::Byte,
+        "one:{0} byte|other:{0} bytes",
+    ),
+    (
+        "es",
+        UnitWidth::Long,
+        Unit::Kilobyte,
+        "one:{0} kilobyte|other:{0} kilobytes",
+    ),
+    (
+        "es",
+        UnitWidth::Long,
+        Unit::Megabyte,
+        "one:{0} megabyte|other:{0} megabytes",
+    ),
+    (
+        "es",
+        UnitWidth::Long,
+        Unit::Gigabyte,
+        "one:{0} gigabyte|other:{0} gigabytes",
+    ),
+    (
+        "es",
+        UnitWidth::Long,
+        Unit::Terabyte,
+        "one:{0} terabyte|other:{0} terabytes",
+    ),
+    (
+        "es",
+        UnitWidth::Long,
+        Unit::Second,
+        "one:{0} segundo|other:{0} segundos",
+    ),
+    (
+        "es",
+        UnitWidth::Long,
+        Unit::Minute,
+        "one:{0} minuto|other:{0} minutos",
+    ),
+    (
+        "es",
+        UnitWidth::Long,
+        Unit::Hour,
+        "one:{0} hora|other:{0} horas",
+    ),
+    (
+        "es",
+        UnitWidth::Long,
+        Unit::Day,
+        "one:{0} día|other:{0} días",
+    ),
+    (
+        "es",
+        UnitWidth::Long,
+        Unit::Week,
+        "one:{0} semana|other:{0} semanas",
+    ),
+    (
+        "es",
+        UnitWidth::Long,
+        Unit::Centimeter,
+        "one:{0} centímetro|other:{0} centímetros",
+    ),
+    (
+        "es",
+        UnitWidth::Long,
+        Unit::Meter,
+        "one:{0} metro|other:{0} metros",
+    ),
+    (
+        "es",
+        UnitWidth::Long,
+        Unit::Kilometer,
+        "one:{0} kilómetro|other:{0} kilómetros",
+    ),
+    (
+        "es",
+        UnitWidth::Long,
+        Unit::Mile,
+        "one:{0} milla|other:{0} millas",
+    ),
+    ("ru", UnitWidth::Short, Unit::Byte, "{0} Б"),
+    ("ru", UnitWidth::Short, Unit::Kilobyte, "{0} КБ"),
+    ("ru", UnitWidth::Short, Unit::Megabyte, "{0} МБ"),
+    ("ru", UnitWidth::Short, Unit::Gigabyte, "{0} ГБ"),
+    ("ru", UnitWidth::Short, Unit::Terabyte, "{0} ТБ"),
+    ("ru", UnitWidth::Short, Unit::Second, "{0} с"),
+    ("ru", UnitWidth::Short, Unit::Minute, "{0} мин"),
+    ("ru", UnitWidth::Short, Unit::Hour, "{0} ч"),
+    ("ru", UnitWidth::Short, Unit::Day, "{0} дн."),
+    ("ru", UnitWidth::Short, Unit::Week, "{0} нед."),
+    ("ru", UnitWidth::Short, Unit::Centimeter, "{0} см"),
+    ("ru", UnitWidth::Short, Unit::Meter, "{0} м"),
+    ("ru", UnitWidth::Short, Unit::Kilometer, "{0} км"),
+    ("ru", UnitWidth::Short, Unit::Mile, "{0} ми"),
+    (
+        "ru",
+        UnitWidth::Long,
+        Unit::Byte,
+        "one:{0} байт|few:{0} байта|many:{0} байт|other:{0} байта",
+    ),
+    (
+        "ru",
+        UnitWidth::Long,
+        Unit::Kilobyte,
+        "one:{0} килобайт|few:{0} килобайта|many:{0} килобайт|other:{0} килобайта",
+    ),
+    (
+        "ru",
+        UnitWidth::Long,
+        Unit::Megabyte,
+        "one:{0} мегабайт|few:{0} мегабайта|many:{0} мегабайт|other:{0} мегабайта",
+    ),
+    (
+        "ru",
+        UnitWidth::Long,
+        Unit::Gigabyte,
+        "one:{0} гигабайт|few:{0} гигабайта|many:{0} гигабайт|other:{0} гигабайта",
+    ),
+    (
+        "ru",
+        UnitWidth::Long,
+        Unit::Terabyte,
+        "one:{0} терабайт|few:{0} терабайта|many:{0} терабайт|other:{0} терабайта",
+    ),
+    (
+        "ru",
+        UnitWidth::Long,
+        Unit::Second,
+        "one:{0} секунда|few:{0} секунды|many:{0} секунд|other:{0} секунды",
+    ),
+    (
+        "ru",
+        UnitWidth::Long,
+        Unit::Minute,
+        "one:{0} минута|few:{0} минуты|many:{0} минут|other:{0} минуты",
+    ),
+    (
+        "ru",
+        UnitWidth::Long,
+        Unit::Hour,
+        "one:{0} час|few:{0} часа|many:{0} часов|other:{0} часа",
+    ),
+    (
+        "ru",
+        UnitWidth::Long,
+        Unit::Day,
+        "one:{0} день|few:{0} дня|many:{0} дней|other:{0} дня",
+    ),
+    (
+        "ru",
+        UnitWidth::Long,
+        Unit::Week,
+        "one:{0} неделя|few:{0} недели|many:{0} недель|other:{0} недели",
+    ),
+    (
+        "ru",
+        UnitWidth::Long,
+        Unit::Centimeter,
+        "one:{0} сантиметр|few:{0} сантиметра|many:{0} сантиметров|other:{0} сантиметра",
+    ),
+    (
+        "ru",
+        UnitWidth::Long,
+        Unit::Meter,
+        "one:{0} метр|few:{0} метра|many:{0} метров|other:{0} метра",
+    ),
+    (
+        "ru",
+        UnitWidth::Long,
+        Unit::Kilometer,
+        "one:{0} километр|few:{0} километра|many:{0} километров|other:{0} километра",
+    ),
+    (
+        "ru",
+        UnitWidth::Long,
+        Unit::Mile,
+        "one:{0} миля|few:{0} мили|many:{0} миль|other:{0} мили",
+    ),
+];
+
+/// Units used to write a number of bytes, and the number of bytes in each
+const BYTE_UNITS: &[(Unit, f64)] = &[
+    (Unit::Terabyte, 1e12),
+    (Unit::Gigabyte, 1e9),
+    (Unit::Megabyte, 1e6),
+    (Unit::Kilobyte, 1e3),
+    (Unit::Byte, 1.0),
+];
+
+/// Units used to write a duration, and the number of seconds in each
+const DURATION_UNITS: &[(Unit, f64)] = &[
+    (Unit::Week, 604800.0),
+    (Unit::Day, 86400.0),
+    (Unit::Hour, 3600.0),
+    (Unit::Minute, 60.0),
+    (Unit::Second, 1.0),
+];
+
+/// Patterns for a unit by plural category, where `{0}` is the number
+pub type UnitPatterns = HashMap<PluralCategory, String>;
+
+fn parse_patterns(forms: &str) -> UnitPatterns {
+    forms
+        .split('|')
+        .map(|form| match form.split_once(':') {
+            Some((category, pattern)) => (
+                PluralCategory::from_name(category).unwrap_or(PluralCategory::Other),
+                pattern.to_string(),
+            ),
+            None => (PluralCategory::Other, form.to_string()),
+        })
+        .collect()
+}
+
+/// Writes numbers with units of measurement, using the conventions and plural rules of a locale
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnitFormatter {
+    numbers: NumberFormatter,
+    rules: PluralRules,
+    patterns: HashMap<(UnitWidth, Unit), UnitPatterns>,
+}
+
+impl Default for UnitFormatter {
+    fn default() -> Self {
+        Self::new("en")
+    }
+}
+
+impl UnitFormatter {
+    /// Create a formatter using the built-in conventions of a language
+    /// Languages without written out unit names use abbreviations
+    ///
+    /// # Arguments
+    /// * `language` - Language code, such as `fr` or `en-GB`
+    pub fn new(language: &str) -> Self {
+        Self::with_numbers(language, NumberFormatter::new(language))
+    }
+
+    /// Create a formatter using the built-in unit names of a language, and the given number formatter
+    ///
+    /// # Arguments
+    /// * `language` - Language code, such as `fr` or `en-GB`
+    /// * `numbers` - Number formatter to write amounts with
+    pub fn with_numbers(language: &str, numbers: NumberFormatter) -> Self {
+        // Patterns shared by every language first, then the primary language, then the full code
+        let language = language.replace('_', "-");
+        let primary = language.split('-').next().unwrap_or_default();
+        let mut patterns = HashMap::default();
+        for code in ["", primary, &language] {
+            patterns.extend(
+                UNIT_DATA
+                    .iter()
+                    .filter(|(locale, _, _, _)| locale.eq_ignore_ascii_case(code))
+                    .map(|(_, width, unit, forms)| ((*width, *unit), parse_patterns(forms))),
+            );
+        }
+
+        Self {
+            numbers,
+            rules: PluralRules::for_language(&language),
+            patterns,
+        }
+    }
+
+    /// Replace the plural rules used to choose the form of a unit, such as those of another language
+    ///
+    /// # Arguments
+    /// * `rules` - Plural rules to use
+    pub fn with_plural_rules(mut self, rules: PluralRules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// Replace the patterns used for some units
+    ///
+    /// # Arguments
+    /// * `overrides` - Patterns by width and unit name, such as `long` then `kilometer`
+    pub fn with_overrides(
+        mut self,
+        overrides: &HashMap<UnitWidth, HashMap<String, UnitPatterns>>,
+    ) -> Self {
+        for (width, units) in overrides {
+            for (name, patterns) in units {
+                if let Some(unit) = Unit::from_name(name) {
+                    self.patterns.insert((*width, unit), patterns.clone());
+                }
+            }
+        }
+        self
+    }
+
+    /// Write a number with a unit
+    ///
+    /// # Arguments
+    /// * `value` - Number to write
+    /// * `unit` - Unit of the number
+    /// * `width` - How much space to write the unit in
+    pub fn format(&self, value: f64, unit: Unit, width: UnitWidth) -> String {
+        self.format_with_digits(value, unit, width, 3)
+    }
+
+    /// Write a number of bytes, in the largest unit that keeps the number above one
+    /// Units are decimal, so a kilobyte is 1000 bytes
+    ///
+    /// # Arguments
+    /// * `bytes` - Number of bytes
+    /// * `width` - How much space to write the unit in
+    pub fn format_bytes(&self, bytes: u64, width: UnitWidth) -> String {
+        self.format_scaled(bytes as f64, BYTE_UNITS, width)
+    }
+
+    /// Write a duration, in the largest unit that keeps the number above one
+    ///
+    /// # Arguments
+    /// * `duration` - Duration to write
+    /// * `width` - How much space to write the unit in
+    pub fn format_duration(&self, duration: Duration, width: UnitWidth) -> String {
+        self.format_scaled(duration.as_secs_f64(), DURATION_UNITS, width)
+    }
+
+    fn format_scaled(&self, value: f64, units: &[(Unit, f64)], width: UnitWidth) -> String {
+        // The unit is chosen after rounding, so 999,999 bytes is "1 MB" rather than "1,000 kB"
+        let (unit, size, digits) = units
+            .iter()
+            .map(|(unit, size)| (unit, size, if *size == 1.0 { 0 } else { 1 }))
+            .find(|(_, size, digits)| {
+                let scale = 10f64.powi(*digits);
+                (value / *size * scale).round() / scale >= 1.0
+            })
+            .unwrap_or_else(|| {
+                let (unit, size) = &units[units.len() - 1];
+                (unit, size, 0)
+            });
+        self.format_with_digits(value / size, *unit, width, digits as usize)
+    }
+
+    fn format_with_digits(
+        &self,
+        value: f64,
+        unit: Unit,
+        width: UnitWidth,
+        digits: usize,
+    ) -> String {
+        let number = self.numbers.format_fixed(value, 0, digits);
+
+        // The plural form depends on the number as written, so 1.0004 km is "1 kilometer"
+        let written: f64 = format!("{:.*}", digits, value).parse().unwrap_or(value);
+        let category = self.rules.category(written);
+
+        let patterns = match width {
+            UnitWidth::Long => self
+                .patterns
+                .get(&(UnitWidth::Long, unit))
+                .or_else(|| self.patterns.get(&(UnitWidth::Short, unit))),
+            _ => self
+                .patterns
+                .get(&(width, unit))
+                .or_else(|| self.patterns.get(&(UnitWidth::Short, unit))),
+        };
+        let pattern = patterns
+            .and_then(|p| p.get(&category).or_else(|| p.get(&PluralCategory::Other)))
+            .map(String::as_str)
+            .unwrap_or("{0}");
+
+        let pattern = match width {
+            UnitWidth::Narrow if !self.patterns.contains_key(&(width, unit)) => {
+                pattern.replace("{0} ", "{0}")
+            }
+            _ => pattern.to_string(),
+        };
+        pattern.replace("{0}", &number)
+    }
+}
+
+#[cfg(test)]
+mod test_token {
+    use super::*;
+
+    #[test]
+    fn test_format() {
+        let en = UnitFormatter::new("en");
+        assert_eq!(en.format(5.0, Unit::Kilometer, UnitWidth::Short), "5 km");
+        assert_eq!(en.format(5.0, Unit::Kilometer, UnitWidth::Narrow), "5km");
+        assert_eq!(
+            en.format(1.0, Unit::Kilometer, UnitWidth::Long),
+            "1 kilometer"
+        );
+        assert_eq!(
+            en.format(1234.5, Unit::Mile, UnitWidth::Long),
+            "1,234.5 miles"
+        );
+        assert_eq!(
+            UnitFormatter::new("en-GB").format(2.0, Unit::Meter, UnitWidth::Long),
+            "2 metres"
+        );
+
+        let ru = UnitFormatter::new("ru");
+        assert_eq!(ru.format(1.0, Unit::Day, UnitWidth::Long), "1 день");
+        assert_eq!(ru.format(3.0, Unit::Day, UnitWidth::Long), "3 дня");
+        assert_eq!(ru.format(5.0, Unit::Day, UnitWidth::Long), "5 дней");
+        assert_eq!(ru.format(1.5, Unit::Day, UnitWidth::Long), "1,5 дня");
+
+        assert_eq!(
+            UnitFormatter::new("ja").format(3.0, Unit::Hour, UnitWidth::Long),
+            "3 h"
+        );
+    }
+
+    #[test]
+    fn test_scaled() {
+        let en = UnitFormatter::new("en");
+        assert_eq!(en.format_bytes(512, UnitWidth::Short), "512 B");
+        assert_eq!(en.format_bytes(1_000, UnitWidth::Long), "1 kilobyte");
+        assert_eq!(en.format_bytes(1_536_000, UnitWidth::Short), "1.5 MB");
+        assert_eq!(en.format_bytes(999_999, UnitWidth::Short), "1 MB");
+        assert_eq!(en.format_bytes(949_000, UnitWidth::Short), "949 kB");
+        assert_eq!(
+            UnitFormatter::new("fr").format_bytes(2_000_000_000, UnitWidth::Short),
+            "2 Go"
+        );
+        assert_eq!(
+            en.format_duration(Duration::from_secs(5400), UnitWidth::Long),
+            "1.5 hours"
+        );
+        assert_eq!(
+            en.format_duration(Duration::from_secs(0), UnitWidth::Short),
+            "0 sec"
+        );
+    }
+
+    #[test]
+    fn test_overrides() {
+        let overrides = serde_json::from_str(
+            r#"{"long": {"kilometer": {"one": "{0} klick", "other": "{0} klicks"}}}"#,
+        )
+        .unwrap();
+        let en = UnitFormatter::new("en").with_overrides(&overrides);
+        assert_eq!(en.format(1.0, Unit::Kilometer, UnitWidth::Long), "1 klick");
+        assert_eq!(en.format(2.0, Unit::Kilometer, UnitWidth::Long), "2 klicks");
+    }
+}